@@ -1,20 +1,34 @@
 use std::fmt;
+use utilities::edge_tools::Edge;
+use utilities::vertex_tools::Vertex;
 use utilities::*;
 
 use crate::entity::digraph::*;
 use crate::entity::graph::*;
+use crate::entity::graph6;
 use crate::entity::poset::*;
 use crate::entity::*;
 use crate::pattern::*;
 
+use cayley::Group;
+
+mod algebraic;
 mod bowties;
+pub mod catalogue;
+pub mod cayley;
 mod corona;
 mod erdos_renyi;
+pub mod finite_field;
 pub mod from_file;
+mod geometries;
 mod grid;
+mod joins;
 mod products;
 mod random_bfs;
+mod random_cube;
 mod random_digraphs;
+mod random_geometric;
+mod random_networks;
 mod random_planar;
 mod random_posets;
 mod random_regular_bipartite;
@@ -50,6 +64,7 @@ pub enum RandomConstructor {
     Bowties(usize, Degree),
     Regular(Order, Degree),
     RegularIsh(Order, Degree),
+    RegularGirth(Order, Degree, usize),
     DegreeSequence(Vec<Degree>),
     PlanarGons(Order, usize),
     VertexStructured(VertexPattern, usize),
@@ -58,6 +73,15 @@ pub enum RandomConstructor {
     BFSOptimal(Order, usize, f64),
     Spinal(Order, f64, Degree),
     HamiltonPlusMatchings(Order, Degree),
+    TwistedCube(usize),
+    Geometric(Order, f64, bool),
+    KNearest(Order, usize),
+    Delaunay(Order),
+    BarabasiAlbert(Order, usize),
+    WattsStrogatz(Order, Degree, f64),
+    ChungLu(Order, f64, f64),
+    ExpectedDegrees(Vec<f64>),
+    StochasticBlock(Vec<Order>, Vec<Vec<f64>>),
 }
 
 #[derive(Clone)]
@@ -77,17 +101,34 @@ pub enum RawConstructor {
     Octahedron,
     Icosahedron,
     Dodecahedron,
+    ProjectivePlane(usize),
+    GeneralisedQuadrangle(usize),
+    GeneralisedHexagon(usize),
+    Kneser(usize, usize),
+    Johnson(usize, usize),
+    Hamming(usize, usize),
+    Paley(usize),
+    Circulant(Order, Vec<usize>),
 }
 
 #[derive(Clone)]
 pub enum RecursiveConstructor {
     CoronaProduct(Box<Constructor>, Box<Constructor>),
+    Join(Box<Constructor>, Box<Constructor>),
+    DisjointUnion(Box<Constructor>, Box<Constructor>),
 }
 
 #[derive(Clone)]
 pub enum StructuralConstructor {
     GiantComponent,
     TwoCore,
+    LineGraph,
+    Complement,
+    Mycielskian,
+    Power(usize),
+    Subdivision(usize),
+    VertexDeleted(Vertex),
+    EdgeDeleted(Edge),
 }
 
 #[derive(Clone)]
@@ -96,6 +137,7 @@ pub enum PosetConstructor {
     Antichain(Order),
     ChainIntersection(Order, usize),
     CorrelatedIntersection(Order, usize, f64),
+    OfDigraph6(String),
 }
 
 #[derive(Clone)]
@@ -104,6 +146,7 @@ pub enum DigraphConstructor {
     Oriented(Order, f64, f64),
     OutRegular(Order, Degree),
     OutRegularOriented(Order, Degree),
+    CayleyDigraph(Group, Vec<String>),
 }
 
 #[derive(Clone)]
@@ -114,13 +157,38 @@ pub enum Constructor {
     Recursive(RecursiveConstructor),
     Structural(StructuralConstructor, Box<Self>),
     RootedTree(Vec<usize>),
+    Cayley(Group, Vec<String>),
     PosetConstr(PosetConstructor),
     DigraphConstr(DigraphConstructor),
     File(String),
     Serialised(String),
+    Encoded(String),
     Special,
 }
 
+/**
+ * The entries of a bracketed tuple such as (1,2,3).
+ */
+fn parse_tuple(text: &str) -> Vec<&str> {
+    let text = text.trim();
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or(text);
+    split_list(inner).into_iter().map(|x| x.trim()).collect()
+}
+
+/**
+ * The generators of a Cayley graph, which follow the group in the list of
+ * arguments.
+ */
+fn cayley_generators(args: &[&str]) -> Vec<String> {
+    args.iter()
+        .skip(1)
+        .map(|arg| arg.trim().to_owned())
+        .collect()
+}
+
 impl Constructor {
     pub fn of_string(text: &str) -> Self {
         // must be otf func_tion(a, b, c, ...)
@@ -227,6 +295,11 @@ impl Constructor {
                 let degree = Degree::of_string(args[1]);
                 Random(Regular(Order::of_string(args[0]), degree))
             }
+            "regular_girth" | "high_girth" => Random(RegularGirth(
+                Order::of_string(args[0]),
+                Degree::of_string(args[1]),
+                args[2].parse().unwrap(),
+            )),
             "regularish" => {
                 let degree = Degree::of_string(args[1]);
                 Random(RegularIsh(Order::of_string(args[0]), degree))
@@ -282,10 +355,81 @@ impl Constructor {
                 Order::of_string(args[0]),
                 Degree::of_string(args[1]),
             )),
+            "twisted_cube" | "twisted_q" | "tq" => Random(TwistedCube(args[0].parse().unwrap())),
+            "geometric" | "rgg" => Random(Geometric(
+                Order::of_string(args[0]),
+                args[1].parse().unwrap(),
+                false,
+            )),
+            "geometric_torus" | "rgg_torus" => Random(Geometric(
+                Order::of_string(args[0]),
+                args[1].parse().unwrap(),
+                true,
+            )),
+            "k_nearest" | "knn" => Random(KNearest(
+                Order::of_string(args[0]),
+                args[1].parse().unwrap(),
+            )),
+            "delaunay" => Random(Delaunay(Order::of_string(args[0]))),
+            "barabasi_albert" | "ba" => Random(BarabasiAlbert(
+                Order::of_string(args[0]),
+                args[1].parse().unwrap(),
+            )),
+            "watts_strogatz" | "ws" => Random(WattsStrogatz(
+                Order::of_string(args[0]),
+                Degree::of_string(args[1]),
+                args[2].parse().unwrap(),
+            )),
+            "chung_lu" | "cl" => Random(ChungLu(
+                Order::of_string(args[0]),
+                args[1].parse().unwrap(),
+                args[2].parse().unwrap(),
+            )),
+            "expected_degrees" | "chung_lu_weights" => Random(ExpectedDegrees(
+                parse_tuple(args[0])
+                    .into_iter()
+                    .map(|w| w.parse().unwrap())
+                    .collect(),
+            )),
+            "stochastic_block" | "sbm" => {
+                let blocks = parse_tuple(args[0])
+                    .into_iter()
+                    .map(Order::of_string)
+                    .collect();
+                let probs = parse_tuple(args[1])
+                    .into_iter()
+                    .map(|row| {
+                        parse_tuple(row)
+                            .into_iter()
+                            .map(|p| p.parse().unwrap())
+                            .collect()
+                    })
+                    .collect();
+                Random(StochasticBlock(blocks, probs))
+            }
             "giant" => Structural(GiantComponent, Box::new(Self::of_string(args[0]))),
             "2core" | "core" | "two_core" => {
                 Structural(TwoCore, Box::new(Self::of_string(args[0])))
             }
+            "line" => Structural(LineGraph, Box::new(Self::of_string(args[0]))),
+            "complement" | "comp" => Structural(Complement, Box::new(Self::of_string(args[0]))),
+            "mycielskian" | "myc" => Structural(Mycielskian, Box::new(Self::of_string(args[0]))),
+            "power" | "pow" => Structural(
+                Power(args[1].parse().unwrap()),
+                Box::new(Self::of_string(args[0])),
+            ),
+            "subdivision" | "subdivide" => {
+                let k = args.get(1).map_or(1, |k| k.parse().unwrap());
+                Structural(Subdivision(k), Box::new(Self::of_string(args[0])))
+            }
+            "delete_vertex" | "del_v" => Structural(
+                VertexDeleted(Vertex::of_string(args[1])),
+                Box::new(Self::of_string(args[0])),
+            ),
+            "delete_edge" | "del_e" => Structural(
+                EdgeDeleted(Edge::of_string(args[1])),
+                Box::new(Self::of_string(args[0])),
+            ),
             "grid" => Raw(Grid(Order::of_string(args[0]), Order::of_string(args[1]))),
             "complete" | "k" => {
                 let n = Order::of_string(args[0]);
@@ -318,11 +462,37 @@ impl Constructor {
             "octahedron" | "Eu6" => Raw(Octahedron),
             "icosahedron" | "Eu12" => Raw(Icosahedron),
             "dodecahedron" | "Eu20" => Raw(Dodecahedron),
+            "projective_plane" | "pg" => Raw(ProjectivePlane(args[0].parse().unwrap())),
+            "generalised_quadrangle" | "gq" => Raw(GeneralisedQuadrangle(args[0].parse().unwrap())),
+            "generalised_hexagon" | "gh" => Raw(GeneralisedHexagon(args[0].parse().unwrap())),
+            "kneser" => Raw(Kneser(args[0].parse().unwrap(), args[1].parse().unwrap())),
+            "johnson" => Raw(Johnson(args[0].parse().unwrap(), args[1].parse().unwrap())),
+            "hamming" => Raw(Hamming(args[0].parse().unwrap(), args[1].parse().unwrap())),
+            "paley" => Raw(Paley(args[0].parse().unwrap())),
+            "circulant" | "circ" => Raw(Circulant(
+                Order::of_string(args[0]),
+                args.iter()
+                    .skip(1)
+                    .map(|j| j.trim().parse().unwrap())
+                    .collect(),
+            )),
+            "cayley" => Cayley(Group::of_string(args[0]), cayley_generators(&args)),
             "corona" => Recursive(CoronaProduct(
                 Box::new(Self::of_string(args[0])),
                 Box::new(Self::of_string(args[1])),
             )),
+            "join" => Recursive(Join(
+                Box::new(Self::of_string(args[0])),
+                Box::new(Self::of_string(args[1])),
+            )),
+            "union" | "disjoint_union" => Recursive(DisjointUnion(
+                Box::new(Self::of_string(args[0])),
+                Box::new(Self::of_string(args[1])),
+            )),
             "serial" => Serialised(args[0].to_string()),
+            "graph6" | "g6" | "sparse6" | "s6" | "digraph6" | "d6" => {
+                Encoded(args[0].trim().to_string())
+            }
             "chain" => PosetConstr(Chain(Order::of_string(args[0]))),
             "antichain" | "anti" => PosetConstr(Antichain(Order::of_string(args[0]))),
             "intersection" | "inter" => {
@@ -336,6 +506,7 @@ impl Constructor {
                 let prob = args[2].parse().unwrap();
                 PosetConstr(CorrelatedIntersection(order, k, prob))
             }
+            "poset" | "poset6" => PosetConstr(OfDigraph6(args[0].trim().to_string())),
             "digraph" => DigraphConstr(OfGraph(Box::new(Self::of_string(args[0])))),
             "oriented" => {
                 let min_p = args.get(1).map_or(0.0, |str| str.parse().unwrap());
@@ -350,6 +521,10 @@ impl Constructor {
                 Order::of_string(args[0]),
                 Degree::of_string(args[1]),
             )),
+            "cayley_digraph" | "cayley_d" => DigraphConstr(CayleyDigraph(
+                Group::of_string(args[0]),
+                cayley_generators(&args),
+            )),
             str => File(str.to_owned()),
         }
     }
@@ -361,7 +536,6 @@ impl Constructor {
         use RandomConstructor::*;
         use RawConstructor::*;
         use RecursiveConstructor::*;
-        use StructuralConstructor::*;
 
         fn g(g: Graph) -> Entity {
             Entity::Graph(g)
@@ -380,6 +554,7 @@ impl Constructor {
                 g(products::new_product(product, self, &g1, &g2))
             }
             RootedTree(parents) => g(tree::new_rooted(parents)),
+            Cayley(group, gens) => g(cayley::new_cayley(group, gens)),
             Random(Biregular(order, left_deg, right_deg)) => g(
                 random_regular_bipartite::new_biregular(*order, *left_deg, *right_deg),
             ),
@@ -400,6 +575,9 @@ impl Constructor {
             Random(PlanarGons(order, k)) => g(random_planar::k_gon_gluing(*order, *k)),
             Random(Bowties(scale, degree)) => g(bowties::new_bowties(*scale, *degree)),
             Random(Regular(order, degree)) => g(regular::new_regular(order, degree)),
+            Random(RegularGirth(order, degree, girth)) => {
+                g(regular::new_regular_with_girth(*order, *degree, *girth))
+            }
             Random(RegularIsh(order, degree)) => {
                 g(regular::new_approximately_regular(order, degree))
             }
@@ -418,6 +596,23 @@ impl Constructor {
             Random(HamiltonPlusMatchings(order, degree)) => {
                 g(regular::new_hamilton_plus_matchings(*order, *degree))
             }
+            Random(TwistedCube(dimension)) => g(random_cube::new_twisted_cube(*dimension)),
+            Random(Geometric(order, radius, torus)) => {
+                g(random_geometric::new_geometric(*order, *radius, *torus))
+            }
+            Random(KNearest(order, k)) => g(random_geometric::new_k_nearest(*order, *k)),
+            Random(Delaunay(order)) => g(random_geometric::new_delaunay(*order)),
+            Random(BarabasiAlbert(order, m)) => g(random_networks::new_barabasi_albert(*order, *m)),
+            Random(WattsStrogatz(order, degree, beta)) => {
+                g(random_networks::new_watts_strogatz(*order, *degree, *beta))
+            }
+            Random(ChungLu(order, avg_deg, exponent)) => {
+                g(random_networks::new_chung_lu(*order, *avg_deg, *exponent))
+            }
+            Random(ExpectedDegrees(weights)) => g(random_networks::new_expected_degrees(weights)),
+            Random(StochasticBlock(blocks, probs)) => {
+                g(random_networks::new_stochastic_block(blocks, probs))
+            }
             Raw(Grid(height, width)) => g(grid::new(height, width)),
             Raw(Complete(order)) => g(Graph::new_complete(*order)),
             Raw(CompleteBipartite(left, right)) => g(Graph::new_complete_bipartite(*left, *right)),
@@ -433,18 +628,32 @@ impl Constructor {
             Raw(Octahedron) => g(raw::new_octahedron()),
             Raw(Icosahedron) => g(raw::new_icosahedron()),
             Raw(Dodecahedron) => g(raw::new_dodecahedron()),
+            Raw(ProjectivePlane(q)) => g(geometries::new_projective_plane(*q)),
+            Raw(GeneralisedQuadrangle(q)) => g(geometries::new_generalised_quadrangle(*q)),
+            Raw(GeneralisedHexagon(q)) => g(geometries::new_generalised_hexagon(*q)),
+            Raw(Kneser(n, k)) => g(algebraic::new_kneser(*n, *k)),
+            Raw(Johnson(n, k)) => g(algebraic::new_johnson(*n, *k)),
+            Raw(Hamming(d, q)) => g(algebraic::new_hamming(*d, *q)),
+            Raw(Paley(q)) => g(algebraic::new_paley(*q)),
+            Raw(Circulant(order, jumps)) => g(algebraic::new_circulant(*order, jumps)),
             Recursive(CoronaProduct(c1, c2)) => {
                 let g1 = c1.new_entity().as_owned_graph();
                 let g2 = c2.new_entity().as_owned_graph();
                 g(corona::new_corona_product(self, &g1, &g2))
             }
-            Structural(GiantComponent, c) => {
-                let g1 = c.new_entity().as_owned_graph();
-                g(strucutral::giant_component(g1))
+            Recursive(Join(c1, c2)) => {
+                let g1 = c1.new_entity().as_owned_graph();
+                let g2 = c2.new_entity().as_owned_graph();
+                g(joins::new_join(self, &g1, &g2))
             }
-            Structural(TwoCore, c) => {
+            Recursive(DisjointUnion(c1, c2)) => {
+                let g1 = c1.new_entity().as_owned_graph();
+                let g2 = c2.new_entity().as_owned_graph();
+                g(joins::new_disjoint_union(self, &g1, &g2))
+            }
+            Structural(transform, c) => {
                 let g1 = c.new_entity().as_owned_graph();
-                g(strucutral::two_core(g1))
+                g(strucutral::new_transformed(transform, self, g1))
             }
             PosetConstr(Chain(order)) => p(Poset::new_chain(*order)),
             PosetConstr(Antichain(order)) => p(Poset::new_antichain(*order)),
@@ -454,6 +663,10 @@ impl Constructor {
             PosetConstr(CorrelatedIntersection(order, k, prob)) => p(
                 random_posets::new_correlated_intersection(*order, *k, *prob),
             ),
+            PosetConstr(OfDigraph6(code)) => {
+                let d = Digraph::of_digraph6(code);
+                p(Poset::of_transitive_closure(d.adj, self.to_owned()))
+            }
             DigraphConstr(OfGraph(constr)) => {
                 let g = constr.new_entity().as_owned_graph();
                 d(Digraph::of_matrix(g.adj, vec![]))
@@ -465,8 +678,10 @@ impl Constructor {
             DigraphConstr(OutRegularOriented(order, deg)) => {
                 d(random_digraphs::new_oriented_out(*order, *deg))
             }
+            DigraphConstr(CayleyDigraph(group, gens)) => d(cayley::new_cayley_digraph(group, gens)),
             File(filename) => from_file::new_entity(filename),
             Serialised(code) => g(Graph::deserialise(code)),
+            Encoded(code) => graph6::new_entity(code),
             Special => panic!("Cannot directly construct Special graph!"),
         }
     }
@@ -475,18 +690,29 @@ impl Constructor {
         use Constructor::*;
         match self {
             Product(_, c1, c2) => c1.is_random() || c2.is_random(),
-            Recursive(RecursiveConstructor::CoronaProduct(c1, c2)) => {
+            Recursive(RecursiveConstructor::CoronaProduct(c1, c2))
+            | Recursive(RecursiveConstructor::Join(c1, c2))
+            | Recursive(RecursiveConstructor::DisjointUnion(c1, c2)) => {
                 c1.is_random() || c2.is_random()
             }
             Structural(_, c) => c.is_random(),
             PosetConstr(poset_constr) => poset_constr.is_random(),
             DigraphConstr(digraph_constr) => digraph_constr.is_random(),
-            RootedTree(_) | Raw(_) | File(_) | Serialised(_) | Special => false,
+            RootedTree(_) | Raw(_) | File(_) | Serialised(_) | Encoded(_) | Special => false,
+            Cayley(_, _) => false,
             Random(_) => true,
         }
     }
 }
 
+/**
+ * The graph built from a constructor string, for use in tests.
+ */
+#[cfg(test)]
+pub fn graph(text: &str) -> Graph {
+    Constructor::of_string(text).new_entity().as_owned_graph()
+}
+
 impl ProductConstructor {
     pub fn all() -> Vec<Self> {
         use ProductConstructor::*;
@@ -500,7 +726,7 @@ impl PosetConstructor {
     pub fn is_random(&self) -> bool {
         use PosetConstructor::*;
         match self {
-            Chain(_) | Antichain(_) => false,
+            Chain(_) | Antichain(_) | OfDigraph6(_) => false,
             ChainIntersection(_, _) | CorrelatedIntersection(_, _, _) => true,
         }
     }
@@ -514,6 +740,7 @@ impl DigraphConstructor {
             Oriented(_, _, _) => true,
             OutRegular(_, _) => true,
             OutRegularOriented(_, _) => true,
+            CayleyDigraph(_, _) => false,
         }
     }
 }
@@ -532,10 +759,19 @@ impl fmt::Display for Constructor {
             RootedTree(parents) => {
                 write!(f, "Rooted tree with parent pattern {:?}", parents)
             }
+            Cayley(group, gens) => {
+                write!(
+                    f,
+                    "Cayley graph of {} generated by {}",
+                    group,
+                    gens.join(", ")
+                )
+            }
             PosetConstr(constr) => write!(f, "{}", constr),
             DigraphConstr(constr) => write!(f, "{}", constr),
             File(filename) => write!(f, "From file {}", filename),
             Serialised(code) => write!(f, "From code {}", code),
+            Encoded(code) => write!(f, "From nauty code {}", code),
             Special => write!(f, "Special"),
         }
     }
@@ -637,6 +873,13 @@ impl fmt::Display for RandomConstructor {
                     *order, *degree
                 )
             }
+            RegularGirth(order, degree, girth) => {
+                write!(
+                    f,
+                    "Random regular graph of order {} and degree {} with girth at least {}",
+                    *order, *degree, girth
+                )
+            }
             RegularIsh(order, degree) => {
                 write!(
                     f,
@@ -682,6 +925,44 @@ impl fmt::Display for RandomConstructor {
                     degree.to_usize() - 2
                 )
             }
+            TwistedCube(dimension) => {
+                write!(f, "Randomly twisted cube of dimension {}", dimension)
+            }
+            Geometric(order, radius, torus) => {
+                let surface = if *torus { "torus" } else { "square" };
+                write!(
+                    f,
+                    "Random geometric graph of order {} with radius {} on the {}",
+                    order, radius, surface
+                )
+            }
+            KNearest(order, k) => {
+                write!(f, "Random {}-nearest-neighbour graph of order {}", k, order)
+            }
+            Delaunay(order) => write!(f, "Random Delaunay triangulation of order {}", order),
+            BarabasiAlbert(order, m) => write!(
+                f,
+                "Barabasi-Albert graph of order {} with {} edges per new vertex",
+                order, m
+            ),
+            WattsStrogatz(order, degree, beta) => write!(
+                f,
+                "Watts-Strogatz graph of order {} and degree {} with rewiring prob {}",
+                order, degree, beta
+            ),
+            ChungLu(order, avg_deg, exponent) => write!(
+                f,
+                "Chung-Lu graph of order {} with average degree {} and exponent {}",
+                order, avg_deg, exponent
+            ),
+            ExpectedDegrees(weights) => {
+                write!(f, "Chung-Lu graph with expected degrees {:?}", weights)
+            }
+            StochasticBlock(blocks, probs) => write!(
+                f,
+                "Stochastic block model with blocks {:?} and probabilities {:?}",
+                blocks, probs
+            ),
         }
     }
 }
@@ -729,6 +1010,16 @@ impl fmt::Display for RawConstructor {
             Octahedron => write!(f, "The Octahedron"),
             Icosahedron => write!(f, "The Icosahedron"),
             Dodecahedron => write!(f, "The Dodecahedron"),
+            ProjectivePlane(q) => write!(f, "Incidence graph of PG(2, {})", q),
+            GeneralisedQuadrangle(q) => write!(f, "Incidence graph of the quadrangle W({})", q),
+            GeneralisedHexagon(q) => write!(f, "Incidence graph of the hexagon H({})", q),
+            Kneser(n, k) => write!(f, "The Kneser graph K({}, {})", n, k),
+            Johnson(n, k) => write!(f, "The Johnson graph J({}, {})", n, k),
+            Hamming(d, q) => write!(f, "The Hamming graph H({}, {})", d, q),
+            Paley(q) => write!(f, "The Paley graph of order {}", q),
+            Circulant(order, jumps) => {
+                write!(f, "Circulant of order {} with jumps {:?}", order, jumps)
+            }
         }
     }
 }
@@ -740,6 +1031,8 @@ impl fmt::Display for RecursiveConstructor {
             CoronaProduct(c1, c2) => {
                 write!(f, "Corona product of ({}) and ({})", c1, c2)
             }
+            Join(c1, c2) => write!(f, "Join of ({}) and ({})", c1, c2),
+            DisjointUnion(c1, c2) => write!(f, "Disjoint union of ({}) and ({})", c1, c2),
         }
     }
 }
@@ -750,6 +1043,13 @@ impl fmt::Display for StructuralConstructor {
         match self {
             GiantComponent => write!(f, "Giant component"),
             TwoCore => write!(f, "2-core"),
+            LineGraph => write!(f, "Line graph"),
+            Complement => write!(f, "Complement"),
+            Mycielskian => write!(f, "Mycielskian"),
+            Power(k) => write!(f, "Power {}", k),
+            Subdivision(k) => write!(f, "{}-subdivision", k),
+            VertexDeleted(v) => write!(f, "Vertex {} deleted", v),
+            EdgeDeleted(e) => write!(f, "Edge {} deleted", e),
         }
     }
 }
@@ -774,6 +1074,7 @@ impl fmt::Display for PosetConstructor {
                     k, prob, order
                 )
             }
+            OfDigraph6(code) => write!(f, "Poset from digraph6 code {}", code),
         }
     }
 }
@@ -790,6 +1091,14 @@ impl fmt::Display for DigraphConstructor {
             OutRegularOriented(n, deg) => {
                 write!(f, "Random order-{} oriented graph of out-deg {}", n, deg)
             }
+            CayleyDigraph(group, gens) => {
+                write!(
+                    f,
+                    "Cayley digraph of {} generated by {}",
+                    group,
+                    gens.join(", ")
+                )
+            }
         }
     }
 }
@@ -0,0 +1,177 @@
+use super::finite_field::*;
+use super::RawConstructor::*;
+use super::*;
+use utilities::vertex_tools::*;
+
+// Vertex-transitive families defined by a relation on some set: subsets,
+// words, field elements or residues. Each takes a list of the elements and
+// joins any two which are related.
+
+fn graph_of_relation<T>(
+    elements: &[T],
+    is_adjacent: impl Fn(&T, &T) -> bool,
+    constructor: RawConstructor,
+) -> Graph {
+    let n = Order::of_usize(elements.len());
+    let mut adj = VertexVec::new(n, &VertexVec::new(n, &false));
+    for (i, x) in elements.iter().enumerate() {
+        for (j, y) in elements.iter().enumerate().skip(i + 1) {
+            if is_adjacent(x, y) {
+                let (u, v) = (Vertex::of_usize(i), Vertex::of_usize(j));
+                adj[u][v] = true;
+                adj[v][u] = true;
+            }
+        }
+    }
+    Graph::of_matrix(adj, Constructor::Raw(constructor))
+}
+
+/**
+ * The k-subsets of {0, ..., n - 1}, as bitmasks in increasing order.
+ */
+fn k_subsets(n: usize, k: usize) -> Vec<u64> {
+    if n >= 64 {
+        panic!("Can only take subsets of a set of fewer than 64 elements!");
+    }
+    if k > n {
+        return vec![];
+    }
+    let mut subsets = vec![];
+    let mut set: u64 = (1 << k) - 1;
+    let last = set << (n - k);
+    loop {
+        subsets.push(set);
+        if set == last {
+            return subsets;
+        }
+        // Gosper's hack: the next number with the same number of ones.
+        let lowest = set & set.wrapping_neg();
+        let ripple = set + lowest;
+        set = (((ripple ^ set) >> 2) / lowest) | ripple;
+    }
+}
+
+/**
+ * The k-subsets of an n-set, adjacent when disjoint. K(5, 2) is the
+ * Petersen graph.
+ */
+pub fn new_kneser(n: usize, k: usize) -> Graph {
+    let sets = k_subsets(n, k);
+    graph_of_relation(&sets, |a, b| a & b == 0, Kneser(n, k))
+}
+
+/**
+ * The k-subsets of an n-set, adjacent when they share k - 1 elements.
+ */
+pub fn new_johnson(n: usize, k: usize) -> Graph {
+    let sets = k_subsets(n, k);
+    graph_of_relation(
+        &sets,
+        |a, b| (a & b).count_ones() as usize + 1 == k,
+        Johnson(n, k),
+    )
+}
+
+/**
+ * The words of length d over an alphabet of size q, adjacent when they
+ * differ in exactly one place.
+ */
+pub fn new_hamming(d: usize, q: usize) -> Graph {
+    let words: Vec<Vec<usize>> = (0..q.pow(d as u32))
+        .map(|x| (0..d).map(|i| (x / q.pow(i as u32)) % q).collect())
+        .collect();
+    graph_of_relation(
+        &words,
+        |a, b| a.iter().zip(b.iter()).filter(|(x, y)| x != y).count() == 1,
+        Hamming(d, q),
+    )
+}
+
+/**
+ * The elements of GF(q), adjacent when their difference is a non-zero
+ * square. We need q = 1 mod 4 so that -1 is a square.
+ */
+pub fn new_paley(q: usize) -> Graph {
+    if q % 4 != 1 {
+        panic!("Paley graphs need q = 1 mod 4, not {}!", q);
+    }
+    let f = FiniteField::new(q);
+    let mut is_square = vec![false; q];
+    for x in 1..q {
+        is_square[f.mul(x, x)] = true;
+    }
+    let elements: Vec<usize> = (0..q).collect();
+    graph_of_relation(&elements, |x, y| is_square[f.sub(*x, *y)], Paley(q))
+}
+
+/**
+ * The residues mod n, with i adjacent to i + j and i - j for each jump j.
+ */
+pub fn new_circulant(order: Order, jumps: &[usize]) -> Graph {
+    let n = order.to_usize();
+    let elements: Vec<usize> = (0..n).collect();
+    graph_of_relation(
+        &elements,
+        |x, y| {
+            let diff = (y + n - x) % n;
+            jumps
+                .iter()
+                .any(|j| diff == j % n || (diff + j).is_multiple_of(n))
+        },
+        Circulant(order, jumps.to_owned()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_k_subsets() {
+        assert_eq!(k_subsets(4, 2), vec![3, 5, 6, 9, 10, 12]);
+        assert_eq!(k_subsets(10, 3).len(), 120);
+        assert_eq!(k_subsets(3, 0), vec![0]);
+        assert_eq!(k_subsets(3, 3), vec![7]);
+    }
+
+    #[test]
+    fn test_kneser_and_johnson() {
+        assert!(new_kneser(5, 2).is_isomorphic_to(&graph("petersen")));
+        assert!(new_johnson(4, 2).is_isomorphic_to(&graph("octahedron")));
+        assert!(new_johnson(5, 2).is_isomorphic_to(&new_kneser(5, 2).complement()));
+        let g = new_kneser(7, 3);
+        assert_eq!(g.n.to_usize(), 35);
+        assert!(g.deg.iter().all(|d| d.to_usize() == 4));
+    }
+
+    #[test]
+    fn test_hamming() {
+        assert!(new_hamming(4, 2).is_isomorphic_to(&graph("q(4)")));
+        assert!(new_hamming(2, 3).is_isomorphic_to(&graph("box(k(3),k(3))")));
+    }
+
+    #[test]
+    fn test_paley() {
+        assert!(new_paley(5).is_isomorphic_to(&graph("c(5)")));
+        assert!(new_paley(9).is_isomorphic_to(&graph("box(k(3),k(3))")));
+        for q in [13, 17, 25, 29] {
+            let g = new_paley(q);
+            assert!(g.deg.iter().all(|d| d.to_usize() == (q - 1) / 2));
+            // Paley graphs are self-complementary.
+            assert!(g.is_isomorphic_to(&g.complement()));
+        }
+    }
+
+    #[test]
+    fn test_circulant() {
+        assert!(new_circulant(Order::of_usize(7), &[1]).is_isomorphic_to(&graph("c(7)")));
+        assert!(graph("circulant(8, 1, 4)")
+            .deg
+            .iter()
+            .all(|d| d.to_usize() == 3));
+        assert!(graph("circulant(6, 1, 2)").is_isomorphic_to(&graph("octahedron")));
+        // The non-zero squares mod 13 are +-1, +-3 and +-4.
+        assert!(graph("circulant(13, 1, 3, 4)").is_isomorphic_to(&new_paley(13)));
+    }
+}
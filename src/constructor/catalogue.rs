@@ -0,0 +1,469 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::constructor::*;
+
+use utilities::component_tools::*;
+
+// This file lists the named graphs we know how to build, so that a graph
+// (usually read from a file) can be recognised as one of them, and so that
+// the kitchen sink can search through them. Products are built up from
+// smaller entries.
+
+fn binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |total, i| total * (n - i) / (i + 1))
+}
+
+/**
+ * The Kneser, Johnson, Hamming and Paley graphs of order n, leaving out
+ * the parameters which give complete or empty graphs or cubes, and the
+ * circulants of order n with jumps 1 and j.
+ */
+pub fn algebraic_of_order(n: usize) -> Vec<RawConstructor> {
+    use RawConstructor::*;
+    let mut raws = vec![];
+    for m in 4..=n.min(63) {
+        for k in 2..=(m / 2) {
+            let num_sets = binomial(m, k);
+            if num_sets > n {
+                break;
+            }
+            if num_sets == n {
+                raws.push(Johnson(m, k));
+                if m > 2 * k {
+                    raws.push(Kneser(m, k));
+                }
+            }
+        }
+    }
+    for q in 3..n {
+        let mut words = q * q;
+        let mut d = 2;
+        while words <= n {
+            if words == n {
+                raws.push(Hamming(d, q));
+            }
+            words *= q;
+            d += 1;
+        }
+    }
+    if n % 4 == 1 && finite_field::as_prime_power(n).is_some() {
+        raws.push(Paley(n));
+    }
+    if n >= 5 {
+        for jump in 2..=(n / 2) {
+            raws.push(Circulant(Order::of_usize(n), vec![1, jump]));
+        }
+    }
+    raws
+}
+
+/**
+ * Every parametrisation of a RawConstructor which gives a graph of order
+ * n, apart from complete multipartite graphs with three or more parts and
+ * circulants with jumps other than those in algebraic_of_order, of which
+ * there are far too many.
+ */
+pub fn raw_of_order(n: usize) -> Vec<RawConstructor> {
+    use RawConstructor::*;
+    let ous = Order::of_usize;
+    let mut raws = vec![Complete(ous(n)), Path(ous(n)), Empty(ous(n))];
+    if n >= 2 {
+        raws.push(Star(ous(n)));
+    }
+    if n >= 3 {
+        raws.push(Cyclic(ous(n)));
+    }
+    for height in 2..n {
+        if height * height > n {
+            break;
+        }
+        if n.is_multiple_of(height) {
+            raws.push(Grid(ous(height), ous(n / height)));
+        }
+    }
+    for left in 1..=(n / 2) {
+        raws.push(CompleteBipartite(ous(left), ous(n - left)));
+    }
+    for chi in 2..n {
+        raws.push(Turan(ous(n), chi));
+    }
+    if n >= 2 && n.is_power_of_two() {
+        raws.push(Cube(n.trailing_zeros() as usize));
+    }
+    if n.is_multiple_of(2) {
+        let cycles = n / 2;
+        for skip in 1..cycles {
+            if 2 * skip >= cycles {
+                break;
+            }
+            raws.push(Petersen(cycles, skip));
+        }
+    }
+    for q in 2..n {
+        if 2 * (q * q + q + 1) > n {
+            break;
+        }
+        if finite_field::as_prime_power(q).is_some() {
+            if 2 * (q * q + q + 1) == n {
+                raws.push(ProjectivePlane(q));
+            }
+            if 2 * (q + 1) * (q * q + 1) == n {
+                raws.push(GeneralisedQuadrangle(q));
+            }
+            if 2 * (q.pow(6) - 1) / (q - 1) == n {
+                raws.push(GeneralisedHexagon(q));
+            }
+        }
+    }
+    raws.append(&mut algebraic_of_order(n));
+    match n {
+        6 => raws.push(Octahedron),
+        7 => raws.push(FanoPlane),
+        12 => raws.push(Icosahedron),
+        20 => raws.push(Dodecahedron),
+        _ => (),
+    }
+    raws
+}
+
+/**
+ * If g is complete multipartite with at least three parts, i.e. its
+ * complement is a disjoint union of at least three cliques, returns it
+ * as a RawConstructor.
+ */
+fn as_complete_multipartite(g: &Graph) -> Option<RawConstructor> {
+    let complement = g.complement();
+    let components = complement.components();
+    let sizes = ComponentVec::<usize>::new_sizes(&components);
+    for v in g.iter_verts() {
+        if complement.deg[v].to_usize() + 1 != *sizes.get(components[v]) {
+            return None;
+        }
+    }
+    let mut parts: Vec<usize> = sizes.iter().copied().filter(|size| *size > 0).collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    parts.sort();
+    Some(RawConstructor::CompleteMultipartite(
+        parts.into_iter().map(Order::of_usize).collect(),
+    ))
+}
+
+/**
+ * The degree of (u1, u2) in a product, where u1 has degree d1 in a graph
+ * of order n1, and is the root (vertex 0) if root1, and likewise for u2.
+ */
+fn product_degree(
+    product: &ProductConstructor,
+    (n1, d1, root1): (usize, usize, bool),
+    (n2, d2, root2): (usize, usize, bool),
+) -> usize {
+    use ProductConstructor::*;
+    match product {
+        Cartesian => d1 + d2,
+        Tensor => d1 * d2,
+        Lex => d1 * n2 + d2,
+        RevLex => d2 * n1 + d1,
+        Strong => d1 + d2 + d1 * d2,
+        Conormal => d1 * n2 + d2 * n1 - d1 * d2,
+        Rooted => d2 + if root2 { d1 } else { 0 },
+        RevRooted => d1 + if root1 { d2 } else { 0 },
+    }
+}
+
+/**
+ * The degree of each vertex of a product, in the order new_product puts
+ * the vertices, so that we can tell which products could match without
+ * building them.
+ */
+fn product_degrees(product: &ProductConstructor, degs1: &[usize], degs2: &[usize]) -> Vec<usize> {
+    let (n1, n2) = (degs1.len(), degs2.len());
+    let mut degrees = Vec::with_capacity(n1 * n2);
+    for (u2, d2) in degs2.iter().copied().enumerate() {
+        for (u1, d1) in degs1.iter().copied().enumerate() {
+            degrees.push(product_degree(
+                product,
+                (n1, d1, u1 == 0),
+                (n2, d2, u2 == 0),
+            ));
+        }
+    }
+    degrees
+}
+
+/**
+ * The smallest range of degrees that the second factor of a product can
+ * have, given its order and the first factor, if every vertex of the
+ * product is to have degree between min_deg and max_deg.
+ */
+fn partner_degrees(
+    product: &ProductConstructor,
+    degs1: &[usize],
+    n2: usize,
+    (min_deg, max_deg): (usize, usize),
+) -> Option<(usize, usize)> {
+    let n1 = degs1.len();
+    let is_possible = |d2: usize, root2: bool| {
+        degs1.iter().enumerate().all(|(u1, d1)| {
+            let d = product_degree(product, (n1, *d1, u1 == 0), (n2, d2, root2));
+            min_deg <= d && d <= max_deg
+        })
+    };
+    let mut possible = (0..n2).filter(|d2| is_possible(*d2, false) || is_possible(*d2, true));
+    let lowest = possible.next()?;
+    Some((lowest, possible.next_back().unwrap_or(lowest)))
+}
+
+/**
+ * Keeps the first constructor of each isomorphism class.
+ */
+pub fn distinct(constructors: Vec<Constructor>) -> Vec<Constructor> {
+    let mut canonical_forms: HashSet<String> = HashSet::new();
+    constructors
+        .into_iter()
+        .filter(|constr| {
+            canonical_forms.insert(constr.new_entity().as_owned_graph().canonical_form())
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct Entry {
+    constructor: Constructor,
+    degrees: Vec<usize>,
+}
+
+impl Entry {
+    fn of_constructor(constructor: Constructor) -> Self {
+        let g = constructor.new_entity().as_owned_graph();
+        let degrees = g.deg.iter().map(|d| d.to_usize()).collect();
+        Self {
+            constructor,
+            degrees,
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.degrees.iter().sum::<usize>() / 2
+    }
+
+    fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self.degrees.to_owned();
+        degrees.sort();
+        degrees
+    }
+
+    fn canonical_form(&self) -> String {
+        self.constructor
+            .new_entity()
+            .as_owned_graph()
+            .canonical_form()
+    }
+}
+
+/**
+ * The named graphs of each order, along with products of smaller ones,
+ * only listing those whose degrees lie in a given range. The range for
+ * the larger factor of a product is worked out from the smaller factor,
+ * so only entries which can still match are ever built. Only one
+ * constructor is kept for each isomorphism class, so that the products
+ * don't get out of hand.
+ */
+struct Catalogue {
+    entries: HashMap<(usize, usize, usize), Vec<Entry>>,
+}
+
+impl Catalogue {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /**
+     * The constructors for graphs of order n with every degree between
+     * min_deg and max_deg, not deduplicated. Every factor of a product has
+     * degree at most that of the product (unless the product is a tensor
+     * product with an empty graph, which we don't bother listing), which
+     * bounds the smaller factor.
+     */
+    fn of_order(&mut self, n: usize, (min_deg, max_deg): (usize, usize)) -> Vec<Entry> {
+        let in_range = |degrees: &[usize]| degrees.iter().all(|d| min_deg <= *d && *d <= max_deg);
+        let mut entries: Vec<Entry> = raw_of_order(n)
+            .into_iter()
+            .map(|raw| Entry::of_constructor(Constructor::Raw(raw)))
+            .filter(|entry| in_range(&entry.degrees))
+            .collect();
+        for factor in 2..n {
+            if factor * factor > n {
+                break;
+            }
+            if !n.is_multiple_of(factor) {
+                continue;
+            }
+            let small = self.distinct_of_order(factor, (0, max_deg)).to_owned();
+            for (i, e1) in small.iter().enumerate() {
+                for product in ProductConstructor::all() {
+                    let Some(range) =
+                        partner_degrees(&product, &e1.degrees, n / factor, (min_deg, max_deg))
+                    else {
+                        continue;
+                    };
+                    // Don't construct duplicate products.
+                    let big = if factor * factor == n {
+                        small[i..]
+                            .iter()
+                            .filter(|e2| e2.degrees.iter().all(|d| range.0 <= *d && *d <= range.1))
+                            .cloned()
+                            .collect()
+                    } else {
+                        self.distinct_of_order(n / factor, range).to_owned()
+                    };
+                    for e2 in big.iter() {
+                        let degrees = product_degrees(&product, &e1.degrees, &e2.degrees);
+                        if in_range(&degrees) {
+                            entries.push(Entry {
+                                constructor: Constructor::Product(
+                                    product.to_owned(),
+                                    Box::new(e1.constructor.to_owned()),
+                                    Box::new(e2.constructor.to_owned()),
+                                ),
+                                degrees,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /**
+     * The entries of order n with degrees in the given range, one per
+     * isomorphism class. Canonical forms are only worked out for entries
+     * whose degree sequences collide.
+     */
+    fn distinct_of_order(&mut self, n: usize, degree_range: (usize, usize)) -> &Vec<Entry> {
+        let key = (n, degree_range.0, degree_range.1);
+        if !self.entries.contains_key(&key) {
+            let mut classes: HashMap<Vec<usize>, Vec<usize>> = HashMap::new();
+            let mut canonical_forms: HashMap<usize, String> = HashMap::new();
+            let mut entries: Vec<Entry> = vec![];
+            for entry in self.of_order(n, degree_range) {
+                let class = classes.entry(entry.degree_sequence()).or_default();
+                if !class.is_empty() {
+                    let form = entry.canonical_form();
+                    let is_new = class.iter().all(|i| {
+                        *canonical_forms
+                            .entry(*i)
+                            .or_insert_with(|| entries[*i].canonical_form())
+                            != form
+                    });
+                    if !is_new {
+                        continue;
+                    }
+                    canonical_forms.insert(entries.len(), form);
+                }
+                class.push(entries.len());
+                entries.push(entry);
+            }
+            self.entries.insert(key, entries);
+        }
+        self.entries.get(&key).unwrap()
+    }
+}
+
+/**
+ * Returns every constructor in the catalogue which builds a graph
+ * isomorphic to g. Candidates are checked by size and degree sequence
+ * before they are built, and is_isomorphic_to compares codegrees before
+ * resorting to canonical forms.
+ */
+pub fn identify(g: &Graph) -> Vec<Constructor> {
+    let mut degrees: Vec<usize> = g.deg.iter().map(|d| d.to_usize()).collect();
+    degrees.sort();
+    let degree_range = (
+        degrees.first().copied().unwrap_or(0),
+        degrees.last().copied().unwrap_or(0),
+    );
+    let mut candidates: Vec<Entry> = Catalogue::new()
+        .of_order(g.n.to_usize(), degree_range)
+        .into_iter()
+        .filter(|entry| entry.size() == g.size() && entry.degree_sequence() == degrees)
+        .collect();
+    if let Some(raw) = as_complete_multipartite(g) {
+        candidates.push(Entry::of_constructor(Constructor::Raw(raw)));
+    }
+
+    candidates
+        .into_iter()
+        .filter(|entry| {
+            let h = entry.constructor.new_entity().as_owned_graph();
+            h.is_isomorphic_to(g)
+        })
+        .map(|entry| entry.constructor)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str) -> Vec<String> {
+        let g = Constructor::of_string(text).new_entity().as_owned_graph();
+        identify(&g).iter().map(|c| format!("{}", c)).collect()
+    }
+
+    #[test]
+    fn test_identify_raw() {
+        let petersen = format!("{}", Constructor::of_string("petersen"));
+        assert!(names("g6(IheA@GUAo)").contains(&petersen));
+
+        let cube = format!("{}", Constructor::of_string("q(4)"));
+        assert!(names("box(q(2),c(4))").contains(&cube));
+
+        let multipartite = format!("{}", Constructor::of_string("k(1,2,3)"));
+        assert!(names("k(2,3,1)").contains(&multipartite));
+
+        let kneser = format!("{}", Constructor::of_string("kneser(5,2)"));
+        assert!(names("petersen").contains(&kneser));
+    }
+
+    #[test]
+    fn test_product_degrees() {
+        for (text1, text2) in [("p(3)", "c(4)"), ("k(1,3)", "petersen"), ("e(2)", "k(3)")] {
+            let c1 = Constructor::of_string(text1);
+            let c2 = Constructor::of_string(text2);
+            let e1 = Entry::of_constructor(c1.to_owned());
+            let e2 = Entry::of_constructor(c2.to_owned());
+            for product in ProductConstructor::all() {
+                let degrees = product_degrees(&product, &e1.degrees, &e2.degrees);
+                let constr =
+                    Constructor::Product(product, Box::new(c1.to_owned()), Box::new(c2.to_owned()));
+                assert_eq!(
+                    Entry::of_constructor(constr.to_owned()).degrees,
+                    degrees,
+                    "{}",
+                    constr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_identify_product() {
+        let found = names("grid(3,5)");
+        assert!(found.iter().any(|name| name.starts_with("Box product of")));
+        let empty = format!("{}", Constructor::of_string("e(9)"));
+        assert!(names("er(9,0.0)").contains(&empty));
+        assert!(!names("er(9,0.0)").contains(&format!("{}", Constructor::of_string("k(9)"))));
+
+        let torus = format!("{}", Constructor::of_string("box(c(8),c(8))"));
+        assert!(names("box(c(8),c(8))").contains(&torus));
+        let cube = format!("{}", Constructor::of_string("q(6)"));
+        let found = names("box(q(3),q(3))");
+        assert!(found.contains(&cube));
+        assert!(found.contains(&format!("{}", Constructor::of_string("box(c(4),q(4))"))));
+    }
+}
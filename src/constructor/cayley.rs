@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::*;
+use utilities::vertex_tools::*;
+
+// Cayley graphs and digraphs of small finite groups. Every element is
+// stored as a vector of numbers: a residue for cyclic groups, (i, j) for
+// r^i s^j in dihedral groups, the images of 0, ..., n - 1 for permutations,
+// and the concatenated coordinates for direct products. The elements of a
+// group are found as the closure of its standard generators.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Group {
+    Cyclic(usize),
+    Dihedral(usize),
+    Symmetric(usize),
+    Alternating(usize),
+    Product(Vec<Group>),
+}
+
+type Element = Vec<usize>;
+
+fn parity(perm: &[usize]) -> usize {
+    let mut seen = vec![false; perm.len()];
+    let mut num_cycles = 0;
+    for start in 0..perm.len() {
+        if !seen[start] {
+            num_cycles += 1;
+            let mut x = start;
+            while !seen[x] {
+                seen[x] = true;
+                x = perm[x];
+            }
+        }
+    }
+    (perm.len() - num_cycles) % 2
+}
+
+/**
+ * Reads a permutation of 0, ..., n - 1 in cycle notation, e.g. (0 1 2)(3 4).
+ */
+fn perm_of_cycles(text: &str, n: usize) -> Element {
+    let mut perm: Element = (0..n).collect();
+    for cycle in text.split(')') {
+        let points: Vec<usize> = cycle
+            .trim()
+            .trim_start_matches('(')
+            .split_whitespace()
+            .map(|x| x.parse().unwrap())
+            .collect();
+        if points.iter().any(|x| *x >= n) {
+            panic!("Cycle ({}) is not a permutation of 0..{}!", cycle, n);
+        }
+        let mut image: Element = (0..n).collect();
+        for (i, x) in points.iter().enumerate() {
+            image[*x] = points[(i + 1) % points.len()];
+        }
+        // Apply the cycles from left to right.
+        perm = perm.iter().map(|x| image[*x]).collect();
+    }
+    perm
+}
+
+impl Group {
+    pub fn of_string(text: &str) -> Self {
+        use Group::*;
+        if let Some((base, power)) = text.rsplit_once('^') {
+            if let Ok(power) = power.trim().parse() {
+                return Product(vec![Self::of_string(base); power]);
+            }
+        }
+        let (func, args) = parse_function_like(text);
+        let n = || args[0].trim().parse().unwrap();
+        match func.trim().to_lowercase().as_str() {
+            "z" | "cyclic" => Cyclic(n()),
+            "d" | "dihedral" => Dihedral(n()),
+            "s" | "sym" | "symmetric" => Symmetric(n()),
+            "a" | "alt" | "alternating" => Alternating(n()),
+            "prod" | "product" => Product(args.iter().map(|arg| Self::of_string(arg)).collect()),
+            str => panic!("Cannot parse group {}!", str),
+        }
+    }
+
+    /**
+     * The length of the vectors representing the elements.
+     */
+    fn len(&self) -> usize {
+        use Group::*;
+        match self {
+            Cyclic(_) => 1,
+            Dihedral(_) => 2,
+            Symmetric(n) | Alternating(n) => *n,
+            Product(factors) => factors.iter().map(|g| g.len()).sum(),
+        }
+    }
+
+    fn identity(&self) -> Element {
+        use Group::*;
+        match self {
+            Cyclic(_) => vec![0],
+            Dihedral(_) => vec![0, 0],
+            Symmetric(n) | Alternating(n) => (0..*n).collect(),
+            Product(factors) => factors.iter().flat_map(|g| g.identity()).collect(),
+        }
+    }
+
+    fn multiply(&self, x: &[usize], y: &[usize]) -> Element {
+        use Group::*;
+        match self {
+            Cyclic(n) => vec![(x[0] + y[0]) % n],
+            Dihedral(n) => {
+                // r^i s^j r^k s^l = r^(i +- k) s^(j + l), since s r = r^-1 s.
+                let k = if x[1] == 0 { y[0] } else { (n - y[0]) % n };
+                vec![(x[0] + k) % n, (x[1] + y[1]) % 2]
+            }
+            // First apply x, then y.
+            Symmetric(_) | Alternating(_) => x.iter().map(|i| y[*i]).collect(),
+            Product(factors) => {
+                let mut product = vec![];
+                let mut start = 0;
+                for g in factors.iter() {
+                    let end = start + g.len();
+                    product.extend(g.multiply(&x[start..end], &y[start..end]));
+                    start = end;
+                }
+                product
+            }
+        }
+    }
+
+    fn standard_generators(&self) -> Vec<Element> {
+        use Group::*;
+        match self {
+            Cyclic(n) => vec![vec![1 % n]],
+            Dihedral(n) => vec![vec![1 % n, 0], vec![0, 1]],
+            Symmetric(n) => {
+                let mut gens = vec![];
+                if *n >= 2 {
+                    gens.push(perm_of_cycles("(0 1)", *n));
+                    gens.push((1..=*n).map(|i| i % n).collect());
+                }
+                gens
+            }
+            Alternating(n) => (2..*n)
+                .map(|i| perm_of_cycles(&format!("(0 1 {})", i), *n))
+                .collect(),
+            Product(factors) => {
+                let identity = self.identity();
+                let mut gens = vec![];
+                let mut start = 0;
+                for g in factors.iter() {
+                    for gen in g.standard_generators() {
+                        let mut element = identity.to_owned();
+                        element[start..start + g.len()].copy_from_slice(&gen);
+                        gens.push(element);
+                    }
+                    start += g.len();
+                }
+                gens
+            }
+        }
+    }
+
+    /**
+     * Reads an element of the group: a number for cyclic groups, a word
+     * in r and s such as r^2s for dihedral groups, cycle notation for
+     * permutations, and a bracketed tuple such as (1, (0 1)) for products.
+     */
+    pub fn element_of_string(&self, text: &str) -> Element {
+        use Group::*;
+        let text = text.trim();
+        match self {
+            Cyclic(n) => {
+                let x: i64 = text.parse().unwrap();
+                vec![x.rem_euclid(*n as i64) as usize]
+            }
+            Dihedral(n) => {
+                let mut element = self.identity();
+                let bytes = text.as_bytes();
+                let mut i = 0;
+                while i < bytes.len() {
+                    let letter = bytes[i];
+                    i += 1;
+                    let mut power = 1;
+                    if i < bytes.len() && bytes[i] == b'^' {
+                        let end = (i + 1..bytes.len())
+                            .find(|j| !bytes[*j].is_ascii_digit())
+                            .unwrap_or(bytes.len());
+                        power = text[i + 1..end].parse().unwrap();
+                        i = end;
+                    }
+                    let factor = match letter {
+                        b'r' => vec![1 % n, 0],
+                        b's' => vec![0, 1],
+                        b'e' => vec![0, 0],
+                        _ => panic!("Cannot parse {} as an element of D_{}!", text, 2 * n),
+                    };
+                    for _ in 0..power {
+                        element = self.multiply(&element, &factor);
+                    }
+                }
+                element
+            }
+            Symmetric(n) => perm_of_cycles(text, *n),
+            Alternating(n) => {
+                let perm = perm_of_cycles(text, *n);
+                if parity(&perm) == 1 {
+                    panic!("{} is an odd permutation, so not in A_{}!", text, n);
+                }
+                perm
+            }
+            Product(factors) => {
+                let inner = text
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .unwrap_or(text);
+                let coords = split_list(inner);
+                if coords.len() != factors.len() {
+                    panic!("{} should have {} coordinates!", text, factors.len());
+                }
+                factors
+                    .iter()
+                    .zip(coords.iter())
+                    .flat_map(|(g, coord)| g.element_of_string(coord))
+                    .collect()
+            }
+        }
+    }
+
+    /**
+     * All the elements of the group, in the order they are found from the
+     * identity, along with the index of each.
+     */
+    fn elements(&self) -> (Vec<Element>, HashMap<Element, usize>) {
+        let identity = self.identity();
+        let gens = self.standard_generators();
+        let mut elements = vec![identity.to_owned()];
+        let mut index = HashMap::new();
+        index.insert(identity, 0);
+        let mut next = 0;
+        while next < elements.len() {
+            for gen in gens.iter() {
+                let product = self.multiply(&elements[next], gen);
+                if !index.contains_key(&product) {
+                    index.insert(product.to_owned(), elements.len());
+                    elements.push(product);
+                }
+            }
+            next += 1;
+        }
+        (elements, index)
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Group::*;
+        match self {
+            Cyclic(n) => write!(f, "Z_{}", n),
+            Dihedral(n) => write!(f, "D_{}", 2 * n),
+            Symmetric(n) => write!(f, "S_{}", n),
+            Alternating(n) => write!(f, "A_{}", n),
+            Product(factors) => {
+                let names: Vec<String> = factors.iter().map(|g| format!("{}", g)).collect();
+                write!(f, "{}", names.join(" x "))
+            }
+        }
+    }
+}
+
+/**
+ * The arcs x -> xs of the Cayley digraph, for each element x and generator
+ * s other than the identity.
+ */
+fn cayley_arcs(group: &Group, generators: &[String]) -> VertexVec<VertexVec<bool>> {
+    let (elements, index) = group.elements();
+    let gens: Vec<Element> = generators
+        .iter()
+        .map(|text| group.element_of_string(text))
+        .filter(|gen| *gen != group.identity())
+        .collect();
+    let n = Order::of_usize(elements.len());
+    let mut adj = VertexVec::new(n, &VertexVec::new(n, &false));
+    for (i, x) in elements.iter().enumerate() {
+        for gen in gens.iter() {
+            let j = *index.get(&group.multiply(x, gen)).unwrap();
+            adj[Vertex::of_usize(i)][Vertex::of_usize(j)] = true;
+        }
+    }
+    adj
+}
+
+pub fn new_cayley(group: &Group, generators: &[String]) -> Graph {
+    let mut adj = cayley_arcs(group, generators);
+    for (u, v) in adj.len().iter_pairs() {
+        let is_edge = adj[u][v] || adj[v][u];
+        adj[u][v] = is_edge;
+        adj[v][u] = is_edge;
+    }
+    Graph::of_matrix(
+        adj,
+        Constructor::Cayley(group.to_owned(), generators.to_owned()),
+    )
+}
+
+pub fn new_cayley_digraph(group: &Group, generators: &[String]) -> Digraph {
+    Digraph::of_matrix(cayley_arcs(group, generators), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_group_orders() {
+        for (text, order) in [
+            ("z(12)", 12),
+            ("d(5)", 10),
+            ("s(4)", 24),
+            ("a(5)", 60),
+            ("z(2)^4", 16),
+            ("prod(z(3), s(3))", 18),
+            ("prod(z(2)^2, d(3))", 24),
+        ] {
+            let (elements, _) = Group::of_string(text).elements();
+            assert_eq!(elements.len(), order, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_cayley_graphs() {
+        assert!(graph("cayley(z(7), 1)").is_isomorphic_to(&graph("c(7)")));
+        let cube = graph("cayley(z(2)^3, (1,0,0), (0,1,0), (0,0,1))");
+        assert!(cube.is_isomorphic_to(&graph("q(3)")));
+        let prism = graph("cayley(d(5), r, s)");
+        assert!(prism.is_isomorphic_to(&graph("petersen(5,1)")));
+        assert!(graph("cayley(s(3), (0 1), (1 2))").is_isomorphic_to(&graph("c(6)")));
+
+        // The truncated tetrahedron, and the permutohedron of order 24.
+        let a4 = graph("cayley(a(4), (0 1 2), (0 1)(2 3))");
+        assert_eq!(a4.n.to_usize(), 12);
+        assert!(a4.deg.iter().all(|d| d.to_usize() == 3));
+        let s4 = graph("cayley(s(4), (0 1), (1 2), (2 3))");
+        assert_eq!(s4.n.to_usize(), 24);
+        assert!(s4.deg.iter().all(|d| d.to_usize() == 3));
+        assert!(s4.is_connected());
+
+        let d = Constructor::of_string("cayley_digraph(z(5), 1, 2)")
+            .new_entity()
+            .as_owned_digraph();
+        assert!(d.out_deg.iter().all(|d| d.to_usize() == 2));
+        assert!(d.is_connected());
+    }
+}
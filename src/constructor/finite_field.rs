@@ -0,0 +1,222 @@
+// Arithmetic in the finite field GF(q), for q = p^k a prime power. An
+// element is stored as a number less than q, whose base-p digits are the
+// coefficients of a polynomial over GF(p) reduced modulo some irreducible
+// polynomial of degree k. Everything is done by table lookup, so this is
+// only meant for small fields.
+
+/**
+ * Returns (p, k) if q = p^k for a prime p and k >= 1.
+ */
+pub fn as_prime_power(q: usize) -> Option<(usize, usize)> {
+    if q < 2 {
+        return None;
+    }
+    let p = (2..=q).find(|d| q.is_multiple_of(*d)).unwrap();
+    let mut rest = q;
+    let mut k = 0;
+    while rest.is_multiple_of(p) {
+        rest /= p;
+        k += 1;
+    }
+    if rest == 1 {
+        Some((p, k))
+    } else {
+        None
+    }
+}
+
+fn digits(x: usize, p: usize, k: usize) -> Vec<usize> {
+    let mut digits = Vec::with_capacity(k);
+    let mut rest = x;
+    for _ in 0..k {
+        digits.push(rest % p);
+        rest /= p;
+    }
+    digits
+}
+
+fn of_digits(digits: &[usize], p: usize) -> usize {
+    digits.iter().rev().fold(0, |x, digit| x * p + digit)
+}
+
+/**
+ * The product of x and y modulo the monic polynomial t^k + modulus(t),
+ * where all of them are given by their coefficients.
+ */
+fn mul_mod(x: &[usize], y: &[usize], modulus: &[usize], p: usize) -> Vec<usize> {
+    let k = modulus.len();
+    let mut product = vec![0; 2 * k];
+    for (i, a) in x.iter().enumerate() {
+        for (j, b) in y.iter().enumerate() {
+            product[i + j] = (product[i + j] + a * b) % p;
+        }
+    }
+    // Replace t^d by -t^(d - k) modulus(t), from the top down.
+    for d in (k..2 * k).rev() {
+        let c = product[d];
+        if c != 0 {
+            product[d] = 0;
+            for (i, m) in modulus.iter().enumerate() {
+                product[d - k + i] = (product[d - k + i] + (p - c) * m) % p;
+            }
+        }
+    }
+    product.truncate(k);
+    product
+}
+
+pub struct FiniteField {
+    pub q: usize,
+    sum: Vec<Vec<usize>>,
+    negative: Vec<usize>,
+    product: Vec<Vec<usize>>,
+    inverse: Vec<usize>,
+}
+
+impl FiniteField {
+    pub fn new(q: usize) -> Self {
+        let (p, k) = match as_prime_power(q) {
+            Some(pk) => pk,
+            None => panic!("There is no field with {} elements!", q),
+        };
+        let sum: Vec<Vec<usize>> = (0..q)
+            .map(|x| {
+                let xs = digits(x, p, k);
+                (0..q)
+                    .map(|y| {
+                        let ys = digits(y, p, k);
+                        let zs: Vec<usize> = (0..k).map(|i| (xs[i] + ys[i]) % p).collect();
+                        of_digits(&zs, p)
+                    })
+                    .collect()
+            })
+            .collect();
+        let negative = (0..q)
+            .map(|x| (0..q).find(|y| sum[x][*y] == 0).unwrap())
+            .collect();
+        // Try each monic polynomial of degree k in turn until one gives a
+        // multiplication without zero divisors, i.e. is irreducible.
+        for modulus in 0..q {
+            let ms = digits(modulus, p, k);
+            let product: Vec<Vec<usize>> = (0..q)
+                .map(|x| {
+                    let xs = digits(x, p, k);
+                    (0..q)
+                        .map(|y| of_digits(&mul_mod(&xs, &digits(y, p, k), &ms, p), p))
+                        .collect()
+                })
+                .collect();
+            let is_field = (1..q).all(|x| (1..q).all(|y| product[x][y] != 0));
+            if is_field {
+                let inverse = (0..q)
+                    .map(|x| (0..q).find(|y| product[x][*y] == 1).unwrap_or(0))
+                    .collect();
+                return Self {
+                    q,
+                    sum,
+                    negative,
+                    product,
+                    inverse,
+                };
+            }
+        }
+        panic!("Could not find an irreducible polynomial for GF({})!", q)
+    }
+
+    pub fn add(&self, x: usize, y: usize) -> usize {
+        self.sum[x][y]
+    }
+
+    pub fn neg(&self, x: usize) -> usize {
+        self.negative[x]
+    }
+
+    pub fn sub(&self, x: usize, y: usize) -> usize {
+        self.add(x, self.neg(y))
+    }
+
+    pub fn mul(&self, x: usize, y: usize) -> usize {
+        self.product[x][y]
+    }
+
+    pub fn inv(&self, x: usize) -> usize {
+        if x == 0 {
+            panic!("Cannot invert zero!");
+        }
+        self.inverse[x]
+    }
+
+    pub fn dot(&self, xs: &[usize], ys: &[usize]) -> usize {
+        xs.iter()
+            .zip(ys.iter())
+            .fold(0, |total, (x, y)| self.add(total, self.mul(*x, *y)))
+    }
+
+    /**
+     * Scales the vector so that its first non-zero entry is 1, giving a
+     * canonical representative of the projective point it spans.
+     */
+    pub fn normalise(&self, xs: &[usize]) -> Vec<usize> {
+        match xs.iter().find(|x| **x != 0) {
+            Some(lead) => {
+                let scale = self.inv(*lead);
+                xs.iter().map(|x| self.mul(*x, scale)).collect()
+            }
+            None => xs.to_owned(),
+        }
+    }
+
+    /**
+     * The points of the projective space PG(dimension, q), as normalised
+     * vectors of length dimension + 1.
+     */
+    pub fn projective_points(&self, dimension: usize) -> Vec<Vec<usize>> {
+        let len = dimension + 1;
+        (0..self.q.pow(len as u32))
+            .map(|x| {
+                digits(x, self.q, len)
+                    .into_iter()
+                    .rev()
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|xs| xs.iter().find(|x| **x != 0).is_some_and(|lead| *lead == 1))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prime_powers() {
+        assert_eq!(as_prime_power(1), None);
+        assert_eq!(as_prime_power(7), Some((7, 1)));
+        assert_eq!(as_prime_power(8), Some((2, 3)));
+        assert_eq!(as_prime_power(81), Some((3, 4)));
+        assert_eq!(as_prime_power(12), None);
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        for q in [2, 3, 4, 5, 8, 9, 16, 25, 27] {
+            let f = FiniteField::new(q);
+            for x in 0..q {
+                assert_eq!(f.add(x, f.neg(x)), 0);
+                if x != 0 {
+                    assert_eq!(f.mul(x, f.inv(x)), 1);
+                }
+                for y in 0..q {
+                    assert_eq!(f.mul(x, y), f.mul(y, x));
+                    for z in 0..q {
+                        let left = f.mul(x, f.add(y, z));
+                        assert_eq!(left, f.add(f.mul(x, y), f.mul(x, z)));
+                        assert_eq!(f.mul(x, f.mul(y, z)), f.mul(f.mul(x, y), z));
+                    }
+                }
+            }
+            let points = f.projective_points(2);
+            assert_eq!(points.len(), q * q + q + 1);
+        }
+    }
+}
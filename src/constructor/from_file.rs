@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use super::*;
 use utilities::vertex_tools::*;
@@ -119,19 +120,55 @@ fn new_poset(contents: String, filename: &String) -> Poset {
     Poset::of_ordering(gt, Constructor::File(filename.to_owned()))
 }
 
-pub fn new_entity(filename: &String) -> Entity {
-    let mut pathbuf = std::env::current_exe().unwrap();
-    pathbuf.pop();
-    while !pathbuf.ends_with("combi/") {
-        pathbuf.pop();
+/**
+ * The path of the file manual/{filename}.{extension} in the repo. The repo
+ * is the nearest ancestor of the executable with a manual folder, or failing
+ * that the directory the crate was built from.
+ */
+fn manual_path(filename: &str, extension: &str) -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    let has_manual = |dir: &Path| dir.join("manual").is_dir();
+    let repo = exe
+        .ancestors()
+        .skip(1)
+        .find(|dir| has_manual(dir))
+        .map(Path::to_path_buf)
+        .or_else(|| Some(PathBuf::from(env!("CARGO_MANIFEST_DIR"))).filter(|dir| has_manual(dir)))
+        .unwrap_or_else(|| panic!("Cannot find the manual folder above {}!", exe.display()));
+    repo.join(format!("manual/{}.{}", filename, extension))
+}
+
+/**
+ * A pattern is written as a graph, with extra lines starting with * to
+ * list the highlighted vertices, e.g.
+ * 4
+ * 0 ~ 1, 2, 3
+ * * 3
+ */
+fn new_pattern(contents: String, filename: &str) -> (Graph, Vec<Vertex>) {
+    let mut highlighted = vec![];
+    let mut graph_lines = vec![];
+    for line in contents.trim().lines() {
+        match line.trim().strip_prefix('*') {
+            Some(verts) => highlighted.extend(verts.split(',').map(Vertex::of_string)),
+            None => graph_lines.push(line),
+        }
     }
+    let h = new_graph(graph_lines.join("\n"), &filename.to_owned());
+    (h, highlighted)
+}
 
-    let mut graph_pathbuf = pathbuf.to_owned();
-    graph_pathbuf.push(format!("manual/{}.gph", filename));
-    let mut poset_pathbuf = pathbuf.to_owned();
-    poset_pathbuf.push(format!("manual/{}.pst", filename));
-    let mut digraph_pathbuf = pathbuf.to_owned();
-    digraph_pathbuf.push(format!("manual/{}.dig", filename));
+pub fn read_pattern(filename: &str) -> (Graph, Vec<Vertex>) {
+    match fs::read_to_string(manual_path(filename, "pat")) {
+        Ok(contents) => new_pattern(contents, filename),
+        Err(e) => panic!("Cannot find pattern {} (Error: {})", filename, e),
+    }
+}
+
+pub fn new_entity(filename: &String) -> Entity {
+    let graph_pathbuf = manual_path(filename, "gph");
+    let poset_pathbuf = manual_path(filename, "pst");
+    let digraph_pathbuf = manual_path(filename, "dig");
 
     if graph_pathbuf.exists() {
         println!("Found graph file!");
@@ -156,3 +193,16 @@ pub fn new_entity(filename: &String) -> Entity {
         panic!("Could not find constructor {}", filename)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_pattern() {
+        let contents = "5\n0 ~ 1, 2\n1 ~ 2, 3\n2 ~ 4\n* 3, 4".to_owned();
+        let (h, highlighted) = new_pattern(contents, "test");
+        assert_eq!(h.size(), 5);
+        assert_eq!(highlighted, vec![Vertex::of_usize(3), Vertex::of_usize(4)]);
+    }
+}
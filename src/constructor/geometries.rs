@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+
+use super::finite_field::*;
+use super::*;
+use utilities::vertex_tools::*;
+
+// Point-line incidence graphs of finite geometries: the Desarguesian
+// projective planes, and the classical generalised quadrangles and
+// hexagons. In each incidence graph the points come first, then the lines.
+
+fn incidence_graph(num_points: usize, lines: &[Vec<usize>], constructor: Constructor) -> Graph {
+    let order = Order::of_usize(num_points + lines.len());
+    let mut adj_list: VertexVec<Vec<Vertex>> = VertexVec::new(order, &vec![]);
+    for (i, line) in lines.iter().enumerate() {
+        let l = Vertex::of_usize(num_points + i);
+        for point in line.iter() {
+            let x = Vertex::of_usize(*point);
+            adj_list[x].push(l);
+            adj_list[l].push(x);
+        }
+    }
+    Graph::of_adj_list(adj_list, constructor)
+}
+
+/**
+ * The points on the line through the projective points x and y, as
+ * indices into the list of points.
+ */
+fn line_through(
+    f: &FiniteField,
+    x: &[usize],
+    y: &[usize],
+    index: &HashMap<Vec<usize>, usize>,
+) -> Vec<usize> {
+    let mut line: Vec<usize> = (0..f.q)
+        .map(|lambda| {
+            let point: Vec<usize> = x
+                .iter()
+                .zip(y.iter())
+                .map(|(a, b)| f.add(f.mul(lambda, *a), *b))
+                .collect();
+            *index.get(&f.normalise(&point)).unwrap()
+        })
+        .collect();
+    line.push(*index.get(x).unwrap());
+    line.sort();
+    line
+}
+
+/**
+ * All the lines through two of the points for which are_collinear holds,
+ * where every such line is assumed to be contained in the point set.
+ */
+fn lines_of_points(
+    f: &FiniteField,
+    points: &[Vec<usize>],
+    are_collinear: impl Fn(&[usize], &[usize]) -> bool,
+) -> Vec<Vec<usize>> {
+    let index: HashMap<Vec<usize>, usize> = points
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (x.to_owned(), i))
+        .collect();
+    let mut found: HashSet<Vec<usize>> = HashSet::new();
+    let mut lines = vec![];
+    for (i, x) in points.iter().enumerate() {
+        for y in points.iter().skip(i + 1) {
+            if are_collinear(x, y) {
+                let line = line_through(f, x, y, &index);
+                if found.insert(line.to_owned()) {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    lines
+}
+
+/**
+ * The incidence graph of PG(2, q), which is C4-free with girth 6.
+ */
+pub fn new_projective_plane(q: usize) -> Graph {
+    let f = FiniteField::new(q);
+    let points = f.projective_points(2);
+    // Lines are the points of the dual plane, i.e. a line contains the
+    // points orthogonal to it.
+    let lines: Vec<Vec<usize>> = points
+        .iter()
+        .map(|l| {
+            (0..points.len())
+                .filter(|x| f.dot(l, &points[*x]) == 0)
+                .collect()
+        })
+        .collect();
+    incidence_graph(
+        points.len(),
+        &lines,
+        Constructor::Raw(RawConstructor::ProjectivePlane(q)),
+    )
+}
+
+/**
+ * The incidence graph of the symplectic generalised quadrangle W(q), whose
+ * points are those of PG(3, q) and whose lines are the totally isotropic
+ * lines of the form x0 y1 - x1 y0 + x2 y3 - x3 y2. It has girth 8.
+ */
+pub fn new_generalised_quadrangle(q: usize) -> Graph {
+    let f = FiniteField::new(q);
+    let points = f.projective_points(3);
+    let form = |x: &[usize], y: &[usize]| {
+        let first = f.sub(f.mul(x[0], y[1]), f.mul(x[1], y[0]));
+        let second = f.sub(f.mul(x[2], y[3]), f.mul(x[3], y[2]));
+        f.add(first, second)
+    };
+    let lines = lines_of_points(&f, &points, |x, y| form(x, y) == 0);
+    incidence_graph(
+        points.len(),
+        &lines,
+        Constructor::Raw(RawConstructor::GeneralisedQuadrangle(q)),
+    )
+}
+
+/**
+ * The incidence graph of the split Cayley hexagon H(q). We think of
+ * vectors x = (a, u, v) in GF(q)^7 as split octonions of trace zero, in
+ * Zorn's vector-matrix form [[a, u], [v, -a]]. The points are those on the
+ * quadric a^2 + u.v = 0 (i.e. of norm zero), and two points are collinear
+ * exactly when their octonion product is zero. It has girth 12.
+ */
+pub fn new_generalised_hexagon(q: usize) -> Graph {
+    let f = FiniteField::new(q);
+    let points: Vec<Vec<usize>> = f
+        .projective_points(6)
+        .into_iter()
+        .filter(|x| f.add(f.mul(x[0], x[0]), f.dot(&x[1..4], &x[4..7])) == 0)
+        .collect();
+    let cross = |x: &[usize], y: &[usize]| -> Vec<usize> {
+        (0..3)
+            .map(|i| {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                f.sub(f.mul(x[j], y[k]), f.mul(x[k], y[j]))
+            })
+            .collect()
+    };
+    let is_product_zero = |x: &[usize], y: &[usize]| {
+        let (a, u, v) = (x[0], &x[1..4], &x[4..7]);
+        let (b, s, t) = (y[0], &y[1..4], &y[4..7]);
+        let scalars = f.mul(a, b);
+        if f.add(scalars, f.dot(u, t)) != 0 || f.add(scalars, f.dot(v, s)) != 0 {
+            return false;
+        }
+        let vt = cross(v, t);
+        let us = cross(u, s);
+        (0..3).all(|i| {
+            let top = f.add(f.sub(f.mul(a, s[i]), f.mul(b, u[i])), vt[i]);
+            let bottom = f.sub(f.sub(f.mul(b, v[i]), f.mul(a, t[i])), us[i]);
+            top == 0 && bottom == 0
+        })
+    };
+    let lines = lines_of_points(&f, &points, is_product_zero);
+    incidence_graph(
+        points.len(),
+        &lines,
+        Constructor::Raw(RawConstructor::GeneralisedHexagon(q)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dossier::*;
+    use crate::operation::int_operation::IntOperation;
+
+    /**
+     * Checks that g is the incidence graph of a generalised n-gon of order
+     * (q, q) with the given number of points.
+     */
+    fn check_polygon(g: &Graph, n: usize, q: usize, num_points: usize) {
+        assert_eq!(g.n.to_usize(), 2 * num_points);
+        assert!(g.deg.iter().all(|d| d.to_usize() == q + 1));
+        let mut dossier = Dossier::new(Entity::Graph(g.to_owned()));
+        let mut ann_box = AnnotationsBox::new();
+        let girth = dossier.operate_int(&mut ann_box, &IntOperation::Girth);
+        assert_eq!(girth as usize, 2 * n);
+        assert_eq!(g.diameter() as usize, n);
+    }
+
+    #[test]
+    fn test_projective_planes() {
+        for q in [2, 3, 4, 5, 7, 8, 9] {
+            check_polygon(&new_projective_plane(q), 3, q, q * q + q + 1);
+        }
+    }
+
+    #[test]
+    fn test_generalised_quadrangles() {
+        for q in [2, 3, 4] {
+            check_polygon(&new_generalised_quadrangle(q), 4, q, (q + 1) * (q * q + 1));
+        }
+    }
+
+    #[test]
+    fn test_generalised_hexagons() {
+        // In characteristic 2 the signs in the octonion product make no
+        // difference, so check an odd q as well.
+        check_polygon(&new_generalised_hexagon(2), 6, 2, 63);
+        check_polygon(&new_generalised_hexagon(3), 6, 3, 364);
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+use utilities::vertex_tools::*;
+
+/**
+ * A copy of g followed by a copy of h, with every vertex of the first
+ * joined to every vertex of the second if is_join is set.
+ */
+fn new_sum(constructor: &Constructor, g: &Graph, h: &Graph, is_join: bool) -> Graph {
+    let gn = g.n.to_usize();
+    let order = g.n + h.n;
+    let mut adj_list = VertexVec::new(order, &vec![]);
+
+    for (u, v) in g.iter_pairs() {
+        if g.adj[u][v] {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+    }
+    for (u, v) in h.iter_pairs() {
+        if h.adj[u][v] {
+            adj_list[u.incr_by(gn)].push(v.incr_by(gn));
+            adj_list[v.incr_by(gn)].push(u.incr_by(gn));
+        }
+    }
+    if is_join {
+        for u in g.iter_verts() {
+            for v in h.iter_verts() {
+                adj_list[u].push(v.incr_by(gn));
+                adj_list[v.incr_by(gn)].push(u);
+            }
+        }
+    }
+
+    Graph::of_adj_list(adj_list, constructor.to_owned())
+}
+
+pub fn new_join(constructor: &Constructor, g: &Graph, h: &Graph) -> Graph {
+    new_sum(constructor, g, h, true)
+}
+
+pub fn new_disjoint_union(constructor: &Constructor, g: &Graph, h: &Graph) -> Graph {
+    new_sum(constructor, g, h, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_join_and_union() {
+        assert!(graph("join(e(2),e(3))").is_isomorphic_to(&graph("k(2,3)")));
+        assert!(graph("join(k(1),c(5))").is_isomorphic_to(&graph("join(c(5),k(1))")));
+        let union = graph("union(c(3),c(4))");
+        assert_eq!(union.n.to_usize(), 7);
+        assert_eq!(union.num_components(), 2);
+        assert!(graph("union(e(2),e(3))").is_isomorphic_to(&graph("e(5)")));
+        // Joins and disjoint unions are swapped by taking complements.
+        let join = graph("join(c(4),p(3))");
+        assert!(join.is_isomorphic_to(&graph(
+            "complement(union(complement(c(4)),complement(p(3))))"
+        )));
+    }
+}
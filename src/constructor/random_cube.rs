@@ -0,0 +1,56 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+use super::*;
+use utilities::vertex_tools::*;
+
+/**
+ * A randomly twisted hypercube, as studied by Dudek, Pérez-Giménez,
+ * Prałat, Qi, West and Zhu. The twisted Q_0 is a single vertex, and the
+ * twisted Q_d is made from two independent twisted copies of Q_{d-1} by
+ * joining them with a uniformly random perfect matching. Vertices
+ * 0..2^(d-1) form the first copy, and so on recursively, so each vertex
+ * still has one neighbour in each dimension.
+ */
+pub fn new_twisted_cube(dimension: usize) -> Graph {
+    let n = 1 << dimension;
+    let order = Order::of_usize(n);
+    let mut rng = thread_rng();
+    let mut adj_list: VertexVec<Vec<Vertex>> = VertexVec::new(order, &vec![]);
+    for d in 0..dimension {
+        let half = 1 << d;
+        // Join each pair of adjacent twisted copies of Q_d.
+        for start in (0..n).step_by(2 * half) {
+            let mut partners: Vec<usize> = (start + half..start + 2 * half).collect();
+            partners.shuffle(&mut rng);
+            for (i, y) in partners.into_iter().enumerate() {
+                let x = Vertex::of_usize(start + i);
+                let y = Vertex::of_usize(y);
+                adj_list[x].push(y);
+                adj_list[y].push(x);
+            }
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::TwistedCube(dimension)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_twisted_cube() {
+        for dimension in 0..=6 {
+            let g = new_twisted_cube(dimension);
+            assert_eq!(g.n.to_usize(), 1 << dimension);
+            assert!(g.deg.iter().all(|d| d.to_usize() == dimension));
+            assert!(g.is_connected());
+        }
+        // There is only one way to twist the small cubes.
+        let q2 = raw::new_cube(2);
+        assert!(new_twisted_cube(2).is_isomorphic_to(&q2));
+    }
+}
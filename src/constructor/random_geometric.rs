@@ -0,0 +1,174 @@
+use rand::{thread_rng, Rng};
+use utilities::*;
+
+use super::*;
+use utilities::vertex_tools::*;
+
+// Random graphs on points placed uniformly at random in the unit square.
+// The points are kept as the positions of the graph, so that it can be
+// drawn as it was built.
+
+fn random_points(order: Order) -> VertexVec<(f64, f64)> {
+    let mut rng = thread_rng();
+    order
+        .iter_verts()
+        .map(|_| (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)))
+        .collect()
+}
+
+/**
+ * The distance between two points, either in the square or on the torus
+ * obtained by gluing its opposite sides.
+ */
+fn distance(p: (f64, f64), q: (f64, f64), torus: bool) -> f64 {
+    let mut dx = (p.0 - q.0).abs();
+    let mut dy = (p.1 - q.1).abs();
+    if torus {
+        dx = dx.min(1.0 - dx);
+        dy = dy.min(1.0 - dy);
+    }
+    (dx * dx + dy * dy).sqrt()
+}
+
+/**
+ * Joins any two points at distance at most radius.
+ */
+pub fn new_geometric(order: Order, radius: f64, torus: bool) -> Graph {
+    let points = random_points(order);
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    for (u, v) in order.iter_pairs() {
+        if distance(points[u], points[v], torus) <= radius {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::Geometric(order, radius, torus)),
+    )
+    .with_positions(points)
+}
+
+/**
+ * Joins each point to the k points closest to it, so every vertex has
+ * degree at least k.
+ */
+pub fn new_k_nearest(order: Order, k: usize) -> Graph {
+    let points = random_points(order);
+    let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+    for u in order.iter_verts() {
+        let mut others: Vec<Vertex> = order.iter_verts().filter(|v| *v != u).collect();
+        others.sort_by(|v, w| {
+            let dv = distance(points[u], points[*v], false);
+            let dw = distance(points[u], points[*w], false);
+            dv.total_cmp(&dw)
+        });
+        for v in others.into_iter().take(k) {
+            adj[u][v] = true;
+            adj[v][u] = true;
+        }
+    }
+    Graph::of_matrix(
+        adj,
+        Constructor::Random(RandomConstructor::KNearest(order, k)),
+    )
+    .with_positions(points)
+}
+
+/**
+ * Is there a circle through a and b with none of the other points inside?
+ * The centres of circles through a and b are m + t w, where m is the
+ * midpoint and w is perpendicular to b - a, and each other point p rules
+ * out a half-line of values of t.
+ */
+fn is_delaunay_edge(points: &VertexVec<(f64, f64)>, a: Vertex, b: Vertex) -> bool {
+    let (pa, pb) = (points[a], points[b]);
+    let m = ((pa.0 + pb.0) / 2.0, (pa.1 + pb.1) / 2.0);
+    let w = (pa.1 - pb.1, pb.0 - pa.0);
+    let sq_dist = |p: (f64, f64), q: (f64, f64)| (p.0 - q.0).powi(2) + (p.1 - q.1).powi(2);
+    let mut min_t = f64::NEG_INFINITY;
+    let mut max_t = f64::INFINITY;
+    for (v, p) in points.iter_enum() {
+        if v == a || v == b {
+            continue;
+        }
+        // p is outside the circle with centre m + t w when alpha + beta t > 0.
+        let alpha = sq_dist(m, *p) - sq_dist(m, pa);
+        let beta = 2.0 * (w.0 * (pa.0 - p.0) + w.1 * (pa.1 - p.1));
+        if beta > 0.0 {
+            min_t = min_t.max(-alpha / beta);
+        } else if beta < 0.0 {
+            max_t = max_t.min(-alpha / beta);
+        } else if alpha <= 0.0 {
+            return false;
+        }
+        if min_t >= max_t {
+            return false;
+        }
+    }
+    true
+}
+
+/**
+ * The Delaunay triangulation of random points, which is planar and joins
+ * two points exactly when some circle through them has no other point
+ * inside it. Takes O(n^3) time.
+ */
+pub fn new_delaunay(order: Order) -> Graph {
+    let points = random_points(order);
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    for (u, v) in order.iter_pairs() {
+        if is_delaunay_edge(&points, u, v) {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::Delaunay(order)),
+    )
+    .with_positions(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometric() {
+        for torus in [false, true] {
+            let g = new_geometric(Order::of_usize(60), 0.2, torus);
+            let points = g.positions.as_ref().unwrap();
+            for (u, v) in g.iter_pairs() {
+                let is_close = distance(points[u], points[v], torus) <= 0.2;
+                assert_eq!(g.adj[u][v], is_close);
+            }
+        }
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let g = new_k_nearest(Order::of_usize(50), 3);
+        assert!(g.deg.iter().all(|d| d.to_usize() >= 3));
+    }
+
+    #[test]
+    fn test_delaunay() {
+        for n in [3, 10, 40, 100] {
+            let g = new_delaunay(Order::of_usize(n));
+            // A triangulation of n points in general position has 3n - 3 - h
+            // edges, where the convex hull has h points.
+            assert!(g.is_connected());
+            assert!(g.size() <= 3 * n - 6 || n == 3);
+            assert!(g.size() >= 2 * n - 3);
+            assert!(g.deg.iter().all(|d| d.to_usize() >= 2));
+        }
+        // Four points in convex position give a 4-cycle and one diagonal.
+        let points = VertexVec::of_vec(vec![(0.0, 0.0), (1.0, 0.1), (0.9, 1.0), (0.1, 0.8)]);
+        let edges = Order::of_usize(4)
+            .iter_pairs()
+            .filter(|(u, v)| is_delaunay_edge(&points, *u, *v))
+            .count();
+        assert_eq!(edges, 5);
+    }
+}
@@ -0,0 +1,252 @@
+use rand::{thread_rng, Rng};
+use utilities::*;
+
+use super::*;
+use utilities::vertex_tools::*;
+
+// Random models of "real-world" networks, with heavy-tailed degrees,
+// short paths with lots of triangles, or community structure.
+
+/**
+ * Barabasi-Albert preferential attachment. We start with a clique on
+ * m + 1 vertices, and each later vertex is joined to m distinct earlier
+ * vertices, picked with probability proportional to their degree.
+ */
+pub fn new_barabasi_albert(order: Order, m: usize) -> Graph {
+    if m == 0 {
+        panic!("Each new vertex needs at least one edge!");
+    }
+    let n = order.to_usize();
+    let mut rng = thread_rng();
+    let mut adj_list: VertexVec<Vec<Vertex>> = VertexVec::new(order, &vec![]);
+    // Each vertex appears here once for each edge it is in, so picking a
+    // uniform entry picks a vertex with probability proportional to degree.
+    let mut ends: Vec<Vertex> = vec![];
+    let seed = Order::of_usize(n.min(m + 1));
+    for (u, v) in seed.iter_pairs() {
+        adj_list[u].push(v);
+        adj_list[v].push(u);
+        ends.push(u);
+        ends.push(v);
+    }
+    for v in order.iter_verts().skip(m + 1) {
+        let mut targets: Vec<Vertex> = vec![];
+        while targets.len() < m {
+            let u = ends[rng.gen_range(0..ends.len())];
+            if !targets.contains(&u) {
+                targets.push(u);
+            }
+        }
+        for u in targets {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+            ends.push(u);
+            ends.push(v);
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::BarabasiAlbert(order, m)),
+    )
+}
+
+/**
+ * Watts-Strogatz small world. Each vertex of a cycle is joined to the
+ * degree / 2 vertices either side of it, and then each of these edges has
+ * its far end moved to a uniformly random new place with probability beta.
+ */
+pub fn new_watts_strogatz(order: Order, degree: Degree, beta: f64) -> Graph {
+    let n = order.to_usize();
+    let k = degree.to_usize();
+    if k % 2 == 1 || k >= n {
+        panic!("Watts-Strogatz needs an even degree less than the order!");
+    }
+    let mut rng = thread_rng();
+    let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+    let mut deg = VertexVec::new(order, &0);
+    for u in order.iter_verts() {
+        for jump in 1..=(k / 2) {
+            let v = u.incr_by(jump).rem(n);
+            adj[u][v] = true;
+            adj[v][u] = true;
+        }
+        deg[u] = k;
+    }
+    for jump in 1..=(k / 2) {
+        for u in order.iter_verts() {
+            let v = u.incr_by(jump).rem(n);
+            if deg[u] == n - 1 || !rng.gen_bool(beta) {
+                continue;
+            }
+            let mut w = Vertex::of_usize(rng.gen_range(0..n));
+            while w == u || adj[u][w] {
+                w = Vertex::of_usize(rng.gen_range(0..n));
+            }
+            adj[u][v] = false;
+            adj[v][u] = false;
+            adj[u][w] = true;
+            adj[w][u] = true;
+            deg[v] -= 1;
+            deg[w] += 1;
+        }
+    }
+    Graph::of_matrix(
+        adj,
+        Constructor::Random(RandomConstructor::WattsStrogatz(order, degree, beta)),
+    )
+}
+
+/**
+ * The Chung-Lu expected degree model: u and v are joined with probability
+ * min(1, w_u w_v / W), where W is the total weight, so that the expected
+ * degree of each vertex is roughly its weight.
+ */
+pub fn new_expected_degrees(weights: &[f64]) -> Graph {
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        panic!("The weights must be finite and non-negative!");
+    }
+    let order = Order::of_usize(weights.len());
+    let weights: VertexVec<f64> = weights.iter().copied().collect();
+    let total: f64 = weights.iter().sum();
+    let mut rng = thread_rng();
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    // With no weight at all there are no edges, rather than 0 / 0.
+    if total > 0.0 {
+        for (u, v) in order.iter_pairs() {
+            let p = (weights[u] * weights[v] / total).min(1.0);
+            if rng.gen_bool(p) {
+                adj_list[u].push(v);
+                adj_list[v].push(u);
+            }
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::ExpectedDegrees(
+            weights.iter().copied().collect(),
+        )),
+    )
+}
+
+/**
+ * Chung-Lu with power-law weights: vertex i has weight proportional to
+ * (i + 1)^(-1 / (exponent - 1)), scaled to have mean avg_deg, so the
+ * degrees follow a power law with the given exponent.
+ */
+pub fn new_chung_lu(order: Order, avg_deg: f64, exponent: f64) -> Graph {
+    if exponent <= 1.0 {
+        panic!("The power-law exponent must be more than 1!");
+    }
+    if avg_deg.is_nan() || avg_deg <= 0.0 {
+        panic!("The average degree must be positive!");
+    }
+    let raw: Vec<f64> = order
+        .iter_verts()
+        .enumerate()
+        .map(|(i, _)| ((i + 1) as f64).powf(-1.0 / (exponent - 1.0)))
+        .collect();
+    let raw_total: f64 = raw.iter().sum();
+    let scale = avg_deg * (order.to_usize() as f64) / raw_total;
+    let weights: Vec<f64> = raw.iter().map(|w| w * scale).collect();
+    let g = new_expected_degrees(&weights);
+    Graph::of_adj_list(
+        g.adj_list,
+        Constructor::Random(RandomConstructor::ChungLu(order, avg_deg, exponent)),
+    )
+}
+
+/**
+ * The stochastic block model. The vertices are split into consecutive
+ * blocks of the given sizes, and a vertex in block i is joined to one in
+ * block j with probability probs[i][j].
+ */
+pub fn new_stochastic_block(blocks: &[Order], probs: &[Vec<f64>]) -> Graph {
+    let k = blocks.len();
+    if probs.len() != k || probs.iter().any(|row| row.len() != k) {
+        panic!("Need a {} x {} matrix of probabilities!", k, k);
+    }
+    for (i, row) in probs.iter().enumerate() {
+        for (j, p) in row.iter().enumerate() {
+            if *p != probs[j][i] {
+                panic!("The matrix of probabilities must be symmetric!");
+            }
+        }
+    }
+    let order = Order::of_usize(blocks.iter().map(|b| b.to_usize()).sum());
+    let mut block: VertexVec<usize> = VertexVec::new(order, &0);
+    let mut v = Vertex::ZERO;
+    for (i, size) in blocks.iter().enumerate() {
+        for _ in 0..size.to_usize() {
+            block[v] = i;
+            v.incr_inplace();
+        }
+    }
+
+    let mut rng = thread_rng();
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    for (u, v) in order.iter_pairs() {
+        if rng.gen_bool(probs[block[u]][block[v]]) {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+    }
+    Graph::of_adj_list(
+        adj_list,
+        Constructor::Random(RandomConstructor::StochasticBlock(
+            blocks.to_owned(),
+            probs.to_owned(),
+        )),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_barabasi_albert() {
+        for m in 1..=3 {
+            let g = new_barabasi_albert(Order::of_usize(100), m);
+            assert_eq!(g.size(), m * (m + 1) / 2 + (100 - m - 1) * m);
+            assert!(g.is_connected());
+            assert!(g.deg.iter().all(|d| d.to_usize() >= m));
+        }
+        // With one edge per vertex we get a tree.
+        assert_eq!(graph("ba(50,1)").size(), 49);
+    }
+
+    #[test]
+    fn test_watts_strogatz() {
+        let lattice = graph("ws(12,4,0.0)");
+        assert!(lattice.is_isomorphic_to(&graph("circulant(12,1,2)")));
+        for beta in [0.3, 1.0] {
+            let g = new_watts_strogatz(Order::of_usize(50), Degree::of_usize(6), beta);
+            assert_eq!(g.size(), 150);
+        }
+    }
+
+    #[test]
+    fn test_chung_lu() {
+        let g = new_chung_lu(Order::of_usize(2000), 6.0, 2.5);
+        let avg_deg = 2.0 * (g.size() as f64) / 2000.0;
+        assert!(avg_deg > 4.0 && avg_deg < 8.0);
+        // The first vertex has by far the largest weight.
+        assert!(g.deg[Vertex::ZERO].to_usize() > 30);
+
+        // Weights of zero give isolated vertices, and large enough weights
+        // give a clique.
+        assert_eq!(graph("expected_degrees((0,0,0,0))").size(), 0);
+        let g = graph("expected_degrees((0,9,9,9,9,9,9,9,9,9))");
+        assert!(g.deg[Vertex::ZERO].to_usize() == 0);
+        assert_eq!(g.size(), 36);
+    }
+
+    #[test]
+    fn test_stochastic_block() {
+        let cliques = graph("sbm((3,4),((1,0),(0,1)))");
+        assert!(cliques.is_isomorphic_to(&graph("union(k(3),k(4))")));
+        let bipartite = graph("sbm((2,3),((0,1),(1,0)))");
+        assert!(bipartite.is_isomorphic_to(&graph("k(2,3)")));
+    }
+}
@@ -124,5 +124,6 @@ pub fn new_biregular(left_order: Order, left_deg: Degree, right_deg: Degree) ->
         constructor: Constructor::Random(crate::constructor::RandomConstructor::Biregular(
             n, left_deg, right_deg,
         )),
+        positions: None,
     }
 }
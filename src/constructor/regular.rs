@@ -133,3 +133,273 @@ pub fn new_hamilton_plus_matchings(n: Order, degree: Degree) -> Graph {
         Random(RandomConstructor::HamiltonPlusMatchings(n, degree)),
     )
 }
+
+const SWITCHES_PER_EDGE: usize = 20;
+
+/**
+ * Breadth-first searches to a bounded depth. Each search stamps the
+ * vertices it reaches instead of clearing a visited array, so a search only
+ * costs as much as the ball it explores.
+ */
+struct ShortPathFinder {
+    stamps: VertexVec<usize>,
+    stamp: usize,
+}
+
+impl ShortPathFinder {
+    fn new(order: Order) -> Self {
+        Self {
+            stamps: VertexVec::new(order, &0),
+            stamp: 0,
+        }
+    }
+
+    /**
+     * Is there a path from u to v of length at most max_len which doesn't
+     * use the edge uv itself?
+     */
+    fn has_short_path(
+        &mut self,
+        adj_list: &VertexVec<Vec<Vertex>>,
+        u: Vertex,
+        v: Vertex,
+        max_len: usize,
+    ) -> bool {
+        self.stamp += 1;
+        self.stamps[u] = self.stamp;
+        let mut frontier = vec![u];
+        for _ in 0..max_len {
+            let mut next = vec![];
+            for x in frontier {
+                for y in adj_list[x].iter() {
+                    if x == u && *y == v {
+                        continue;
+                    }
+                    if *y == v {
+                        return true;
+                    }
+                    if self.stamps[*y] != self.stamp {
+                        self.stamps[*y] = self.stamp;
+                        next.push(*y);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        false
+    }
+}
+
+/**
+ * The fewest vertices a graph of the given degree and girth can have. This
+ * is necessary but not sufficient: e.g. there are no cubic graphs of girth
+ * 7 on 22 vertices, despite the bound being 22.
+ */
+fn moore_bound(degree: usize, girth: usize) -> usize {
+    let k = girth / 2;
+    let sum: usize = (0..k).map(|i| (degree - 1).pow(i as u32)).sum();
+    if girth % 2 == 1 {
+        1 + degree * sum
+    } else {
+        2 * sum
+    }
+}
+
+const PARTNER_TRIES: usize = 20;
+
+const MAX_GREEDY_ATTEMPTS: usize = 100_000;
+
+/**
+ * Adds random edges one at a time between vertices of too small degree,
+ * only ever joining vertices at distance at least girth - 1. Each vertex
+ * tries a few random partners, and only when they all fail do we look
+ * through every possible partner. Gives up if it gets stuck before the
+ * graph is regular.
+ */
+fn greedy_with_girth(order: Order, degree: usize, girth: usize) -> Option<VertexVec<Vec<Vertex>>> {
+    let mut rng = thread_rng();
+    let mut finder = ShortPathFinder::new(order);
+    let mut adj_list: VertexVec<Vec<Vertex>> = VertexVec::new(order, &vec![]);
+    let mut subdeg_verts: Vec<Vertex> = if degree > 0 {
+        order.iter_verts().collect()
+    } else {
+        vec![]
+    };
+    let mut position: VertexVec<usize> = VertexVec::new(order, &0);
+    for (i, v) in subdeg_verts.iter().enumerate() {
+        position[*v] = i;
+    }
+    let max_len = girth.saturating_sub(2);
+    while !subdeg_verts.is_empty() {
+        let u = *subdeg_verts.choose(&mut rng).unwrap();
+        let mut is_allowed = |adj_list: &VertexVec<Vec<Vertex>>, v: Vertex| {
+            v != u && !adj_list[u].contains(&v) && !finder.has_short_path(adj_list, u, v, max_len)
+        };
+        let mut partner = (0..PARTNER_TRIES)
+            .map(|_| *subdeg_verts.choose(&mut rng).unwrap())
+            .find(|v| is_allowed(&adj_list, *v));
+        if partner.is_none() {
+            let candidates: Vec<Vertex> = subdeg_verts
+                .iter()
+                .copied()
+                .filter(|v| is_allowed(&adj_list, *v))
+                .collect();
+            partner = Some(*candidates.choose(&mut rng)?);
+        }
+        let v = partner.unwrap();
+        adj_list[u].push(v);
+        adj_list[v].push(u);
+        for w in [u, v] {
+            if adj_list[w].len() == degree {
+                let i = position[w];
+                subdeg_verts.swap_remove(i);
+                if let Some(moved) = subdeg_verts.get(i) {
+                    position[*moved] = i;
+                }
+            }
+        }
+    }
+    Some(adj_list)
+}
+
+/**
+ * Runs the switching chain: pick two oriented edges u1v1 and u2v2 uniformly
+ * at random and replace them by u1u2 and v1v2, unless this creates a
+ * multiple edge or a cycle shorter than the girth. Every switch has the
+ * same probability as its reverse, so the uniform distribution on regular
+ * graphs of at least this girth is stationary.
+ */
+fn switch_randomly(adj_list: &mut VertexVec<Vec<Vertex>>, girth: usize, num_switches: usize) {
+    let mut rng = thread_rng();
+    let mut finder = ShortPathFinder::new(adj_list.len());
+    let n = adj_list.len().to_usize();
+    let random_arc = |rng: &mut rand::rngs::ThreadRng, adj_list: &VertexVec<Vec<Vertex>>| {
+        let u = Vertex::of_usize(rng.gen_range(0..n));
+        adj_list[u].choose(rng).map(|v| (u, *v))
+    };
+    for _ in 0..num_switches {
+        let (Some((u1, v1)), Some((u2, v2))) = (
+            random_arc(&mut rng, adj_list),
+            random_arc(&mut rng, adj_list),
+        ) else {
+            return;
+        };
+        if [u1, v1].contains(&u2)
+            || [u1, v1].contains(&v2)
+            || adj_list[u1].contains(&u2)
+            || adj_list[v1].contains(&v2)
+        {
+            continue;
+        }
+        let swap = |adj_list: &mut VertexVec<Vec<Vertex>>,
+                    old: [(Vertex, Vertex); 2],
+                    new: [(Vertex, Vertex); 2]| {
+            for (x, y) in old {
+                adj_list[x].retain(|z| *z != y);
+                adj_list[y].retain(|z| *z != x);
+            }
+            for (x, y) in new {
+                adj_list[x].push(y);
+                adj_list[y].push(x);
+            }
+        };
+        swap(adj_list, [(u1, v1), (u2, v2)], [(u1, u2), (v1, v2)]);
+        let max_len = girth.saturating_sub(2);
+        if finder.has_short_path(adj_list, u1, u2, max_len)
+            || finder.has_short_path(adj_list, v1, v2, max_len)
+        {
+            swap(adj_list, [(u1, u2), (v1, v2)], [(u1, v1), (u2, v2)]);
+        }
+    }
+}
+
+/**
+ * A random d-regular graph with girth at least the given value. We find
+ * one greedily, restarting whenever we get stuck, and then run the
+ * switching chain for a while so that the result is close to uniform. The
+ * greedy start rarely gets stuck when the order is well above the size of
+ * the smallest such graph, but can take many attempts near it, and there
+ * may be no such graph at all even when the Moore bound allows one, so we
+ * give up after MAX_GREEDY_ATTEMPTS restarts.
+ */
+pub fn new_regular_with_girth(order: Order, degree: Degree, girth: usize) -> Graph {
+    let n = order.to_usize();
+    let d = degree.to_usize();
+    if d >= n || (n * d) % 2 == 1 {
+        panic!("No {}-regular graphs of order {}!", d, n);
+    }
+    if d >= 2 && n < moore_bound(d, girth) {
+        panic!(
+            "A {}-regular graph of girth {} needs at least {} vertices!",
+            d,
+            girth,
+            moore_bound(d, girth)
+        );
+    }
+    let mut adj_list = (0..MAX_GREEDY_ATTEMPTS)
+        .find_map(|_| greedy_with_girth(order, d, girth))
+        .unwrap_or_else(|| {
+            panic!(
+                "No {}-regular graph of order {} and girth {} found after {} attempts!",
+                d, n, girth, MAX_GREEDY_ATTEMPTS
+            )
+        });
+    switch_randomly(&mut adj_list, girth, SWITCHES_PER_EDGE * n * d / 2);
+    Graph::of_adj_list(
+        adj_list,
+        Random(RandomConstructor::RegularGirth(order, degree, girth)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_girth_at_least(g: &Graph, girth: usize) -> bool {
+        let mut finder = ShortPathFinder::new(g.n);
+        g.iter_edges()
+            .all(|e| !finder.has_short_path(&g.adj_list, e.fst(), e.snd(), girth - 2))
+    }
+
+    #[test]
+    fn test_regular_with_girth() {
+        for (n, d, girth) in [(100, 3, 6), (200, 3, 7), (60, 4, 5), (20, 2, 10)] {
+            let g = new_regular_with_girth(Order::of_usize(n), Degree::of_usize(d), girth);
+            assert!(g.deg.iter().all(|x| x.to_usize() == d));
+            assert!(has_girth_at_least(&g, girth));
+        }
+        let petersen = Constructor::of_string("regular_girth(10,3,5)").new_entity();
+        assert!(petersen.as_owned_graph().is_isomorphic_to(
+            &Constructor::of_string("petersen")
+                .new_entity()
+                .as_owned_graph()
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "found after")]
+    fn test_regular_with_girth_gives_up() {
+        // The Moore bound is 22, but the smallest such graph has 24 vertices.
+        new_regular_with_girth(Order::of_usize(22), Degree::of_usize(3), 7);
+    }
+
+    /**
+     * The labelled cubic graphs of order 6 are 10 copies of K_{3,3} and 60
+     * of the prism, and those of order 8 and girth 4 are 840 cubes and 2520
+     * Mobius ladders. A uniform sampler should get these proportions.
+     */
+    #[test]
+    fn test_regular_with_girth_is_uniform() {
+        let samples = 1400;
+        for (n, girth, rare, expected) in [(6, 3, "k(3,3)", 200), (8, 4, "q(3)", 350)] {
+            let rare = Constructor::of_string(rare).new_entity().as_owned_graph();
+            let count = (0..samples)
+                .filter(|_| {
+                    new_regular_with_girth(Order::of_usize(n), Degree::of_usize(3), girth)
+                        .is_isomorphic_to(&rare)
+                })
+                .count();
+            assert!(count.abs_diff(expected) < 80, "{} of {}", count, samples);
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use queues::*;
 
-use crate::entity::graph::*;
+use crate::constructor::*;
 
 use utilities::component_tools::*;
 use utilities::vertex_tools::*;
 
 /**
  * This file deals with structural constructions, based on other
- * graphs, for example the giant component of G(n,p) or the
- * 2-core of a graph
+ * graphs, for example the giant component of G(n,p), the
+ * 2-core of a graph, or its line graph
  */
 
 /**
@@ -59,3 +59,161 @@ pub fn two_core(g: Graph) -> Graph {
 
     g.of_filtered(&filter)
 }
+
+fn with_constructor(mut g: Graph, constructor: &Constructor) -> Graph {
+    g.constructor = constructor.to_owned();
+    g
+}
+
+/**
+ * The graph whose vertices are the edges of g, adjacent when they share an
+ * endpoint.
+ */
+pub fn line_graph(constructor: &Constructor, g: &Graph) -> Graph {
+    let edges: VertexVec<(Vertex, Vertex)> =
+        VertexVec::of_vec(g.iter_pairs().filter(|(u, v)| g.adj[*u][*v]).collect());
+    let order = edges.len();
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    for (i, j) in order.iter_pairs() {
+        let (e, f) = (edges[i], edges[j]);
+        if e.0 == f.0 || e.0 == f.1 || e.1 == f.0 || e.1 == f.1 {
+            adj_list[i].push(j);
+            adj_list[j].push(i);
+        }
+    }
+    Graph::of_adj_list(adj_list, constructor.to_owned())
+}
+
+/**
+ * The Mycielskian of g, which has the same clique number as g but
+ * chromatic number one larger. Vertex v has a twin v + n joined to the
+ * neighbours of v, and all the twins are joined to a final vertex 2n.
+ */
+pub fn mycielskian(constructor: &Constructor, g: &Graph) -> Graph {
+    let n = g.n.to_usize();
+    let order = Order::of_usize(2 * n + 1);
+    let apex = Vertex::of_usize(2 * n);
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    for (u, v) in g.iter_pairs() {
+        if g.adj[u][v] {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+            adj_list[u].push(v.incr_by(n));
+            adj_list[v.incr_by(n)].push(u);
+            adj_list[v].push(u.incr_by(n));
+            adj_list[u.incr_by(n)].push(v);
+        }
+    }
+    for v in g.iter_verts() {
+        adj_list[v.incr_by(n)].push(apex);
+        adj_list[apex].push(v.incr_by(n));
+    }
+    Graph::of_adj_list(adj_list, constructor.to_owned())
+}
+
+/**
+ * The k-th power of g, in which vertices are adjacent when they are at
+ * distance at most k in g.
+ */
+pub fn power(constructor: &Constructor, g: &Graph, k: usize) -> Graph {
+    let mut adj_list = VertexVec::new(g.n, &vec![]);
+    for u in g.iter_verts() {
+        let dists = g.flood_fill_dist(u);
+        for v in g.iter_verts() {
+            if u != v && dists[v].is_some_and(|d| d as usize <= k) {
+                adj_list[u].push(v);
+            }
+        }
+    }
+    Graph::of_adj_list(adj_list, constructor.to_owned())
+}
+
+/**
+ * Replaces every edge of g by a path through k new vertices.
+ */
+pub fn subdivision(constructor: &Constructor, g: &Graph, k: usize) -> Graph {
+    let n = g.n.to_usize();
+    let order = Order::of_usize(n + k * g.size());
+    let mut adj_list = VertexVec::new(order, &vec![]);
+    let mut next = Vertex::of_usize(n);
+    for (u, v) in g.iter_pairs() {
+        if g.adj[u][v] {
+            let mut prev = u;
+            for _i in 0..k {
+                adj_list[prev].push(next);
+                adj_list[next].push(prev);
+                prev = next;
+                next.incr_inplace();
+            }
+            adj_list[prev].push(v);
+            adj_list[v].push(prev);
+        }
+    }
+    Graph::of_adj_list(adj_list, constructor.to_owned())
+}
+
+/**
+ * Builds the transformation of g described by the constructor.
+ */
+pub fn new_transformed(
+    transform: &StructuralConstructor,
+    constructor: &Constructor,
+    g: Graph,
+) -> Graph {
+    use StructuralConstructor::*;
+    match transform {
+        GiantComponent => giant_component(g),
+        TwoCore => two_core(g),
+        LineGraph => line_graph(constructor, &g),
+        Complement => with_constructor(g.complement(), constructor),
+        Mycielskian => mycielskian(constructor, &g),
+        Power(k) => power(constructor, &g, *k),
+        Subdivision(k) => subdivision(constructor, &g, *k),
+        VertexDeleted(v) => {
+            if !v.less_than(g.n) {
+                panic!("Cannot delete vertex {} from a graph of order {}!", v, g.n);
+            }
+            let mut filter = VertexVec::new(g.n, &true);
+            filter[*v] = false;
+            with_constructor(g.of_filtered(&filter), constructor)
+        }
+        EdgeDeleted(e) => {
+            let mut h = g.to_owned();
+            if !h.adj[e.fst()][e.snd()] {
+                panic!("Cannot delete {}, as it is not an edge!", e);
+            }
+            h.delete_edge(*e);
+            with_constructor(h, constructor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_unary_transforms() {
+        assert!(graph("line(k(4))").is_isomorphic_to(&graph("octahedron")));
+        let line = graph("line(petersen)");
+        assert_eq!(line.n.to_usize(), 15);
+        assert!(line.deg.iter().all(|d| d.to_usize() == 4));
+        assert!(graph("complement(c(5))").is_isomorphic_to(&graph("c(5)")));
+        assert!(graph("power(p(4),3)").is_isomorphic_to(&graph("k(4)")));
+        assert!(graph("power(c(6),2)").deg.iter().all(|d| d.to_usize() == 4));
+        assert!(graph("subdivision(k(3),1)").is_isomorphic_to(&graph("c(6)")));
+        assert!(graph("subdivision(c(4),2)").is_isomorphic_to(&graph("c(12)")));
+        assert!(graph("delete_vertex(c(5),0)").is_isomorphic_to(&graph("p(4)")));
+        assert!(graph("delete_edge(c(5),3~4)").is_isomorphic_to(&graph("p(5)")));
+    }
+
+    #[test]
+    fn test_mycielskian() {
+        // The Mycielskian of C_5 is the Grotzsch graph.
+        let grotzsch = graph("myc(c(5))");
+        assert_eq!(grotzsch.n.to_usize(), 11);
+        assert_eq!(grotzsch.size(), 20);
+        assert!(graph("myc(k(2))").is_isomorphic_to(&graph("c(5)")));
+        assert_eq!(graph("myc(myc(c(5)))").n.to_usize(), 23);
+    }
+}
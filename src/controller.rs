@@ -1,5 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::fs::File;
 use std::io::*;
 use std::time::*;
 
@@ -7,6 +8,7 @@ use rand::rngs::ThreadRng;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use rand::Rng;
+use utilities::component_tools::*;
 use utilities::edge_tools::*;
 use utilities::vertex_tools::*;
 use utilities::*;
@@ -15,7 +17,12 @@ use crate::constructor::*;
 use crate::dossier::AnnotationsBox;
 use crate::dossier::Dossier;
 use crate::entity::graph::Graph;
+use crate::entity::graph6;
 use crate::entity::Entity;
+use crate::generator::digraphs::{self, DigraphFilter};
+use crate::generator::posets::{self, PosetFilter};
+use crate::generator::trees::{self, TreeFilter};
+use crate::generator::{self, Biregular, GraphFilter};
 use crate::operation::int_operation::IntOperation;
 use crate::operation::string_list_operation::StringListOperation;
 
@@ -39,6 +46,12 @@ pub struct LineGraphMetadata {
     pub conditions: Vec<BoolOperation>,
 }
 
+pub struct FilterMetadata {
+    pub input: String,
+    pub output: String,
+    pub conditions: Vec<BoolOperation>,
+}
+
 pub enum Instruction {
     Single(Constructor),
     Repeat(Constructor, usize),
@@ -46,6 +59,8 @@ pub enum Instruction {
     Until(Constructor, Vec<BoolOperation>),
     Forever(Constructor, Vec<BoolOperation>),
     SearchTrees(usize, BoolOperation, Degree),
+    EveryTree(usize, bool, Vec<BoolOperation>),
+    CountTrees(usize, Vec<BoolOperation>),
     KitchenSink(Vec<BoolOperation>),
     KitchenSinkAll(Vec<BoolOperation>),
     Process(Order),
@@ -53,8 +68,15 @@ pub enum Instruction {
     ProcessUntil(Order, Vec<BoolOperation>),
     ProcessUntilDecreasing(Order, IntOperation, Vec<BoolOperation>),
     SearchAll(Order, Vec<BoolOperation>),
+    Every(Order, Vec<BoolOperation>),
+    EveryRegular(Order, Degree, Vec<BoolOperation>),
+    EveryBiregular(Biregular, Vec<BoolOperation>),
+    EveryPoset(Order, Vec<BoolOperation>),
+    EveryDigraph(Order, bool, Vec<BoolOperation>),
     Collate(Constructor, usize, StringListOperation),
     LineGraph(Constructor, LineGraphMetadata),
+    Filter(FilterMetadata),
+    Identify(Constructor),
     Help,
 }
 
@@ -91,6 +113,28 @@ impl fmt::Display for LineGraphMetadata {
     }
 }
 
+impl FilterMetadata {
+    /**
+     * Output goes to stdout when the output file is "-", in which case
+     * everything else we print should go to stderr.
+     */
+    pub fn writes_to_stdout(&self) -> bool {
+        self.output == "-"
+    }
+}
+
+impl fmt::Display for FilterMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let input = if self.input == "-" { "stdin" } else { &self.input };
+        let output = if self.output == "-" { "stdout" } else { &self.output };
+        write!(
+            f,
+            "Filter graphs from {} satisfying {:?}, writing them to {}",
+            input, self.conditions, output
+        )
+    }
+}
+
 impl Instruction {
     pub fn of_string(text: &str) -> Self {
         use Instruction::*;
@@ -130,6 +174,21 @@ impl Instruction {
                     },
                 )
             }
+            "every_tree" | "every_free_tree" | "every_rooted_tree" => EveryTree(
+                args[0].trim().parse().unwrap(),
+                func.trim() == "every_rooted_tree",
+                args.iter()
+                    .skip(1)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
+            "count_trees" => CountTrees(
+                args[0].trim().parse().unwrap(),
+                args.iter()
+                    .skip(1)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
             "kitchen" | "kitchen_sink" | "sink" => KitchenSink(
                 args.iter()
                     .map(|arg| BoolOperation::of_string_result(arg).unwrap())
@@ -164,6 +223,48 @@ impl Instruction {
                     .map(|arg| BoolOperation::of_string_result(arg).unwrap())
                     .collect(),
             ),
+            "every" | "for_every" => Every(
+                Order::of_string(args[0]),
+                args.iter()
+                    .skip(1)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
+            "every_regular" | "every_reg" => EveryRegular(
+                Order::of_string(args[0]),
+                Degree::of_string(args[1]),
+                args.iter()
+                    .skip(2)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
+            "every_biregular" | "every_bireg" => EveryBiregular(
+                Biregular {
+                    left_order: args[0].trim().parse().unwrap(),
+                    left_degree: args[1].trim().parse().unwrap(),
+                    right_order: args[2].trim().parse().unwrap(),
+                    right_degree: args[3].trim().parse().unwrap(),
+                },
+                args.iter()
+                    .skip(4)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
+            "every_poset" | "posets" => EveryPoset(
+                Order::of_string(args[0]),
+                args.iter()
+                    .skip(1)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
+            "every_tournament" | "every_oriented" | "every_oriented_graph" => EveryDigraph(
+                Order::of_string(args[0]),
+                func.trim() == "every_tournament",
+                args.iter()
+                    .skip(1)
+                    .map(|arg| BoolOperation::of_string_result(arg).unwrap())
+                    .collect(),
+            ),
             "collate" => Collate(
                 Constructor::of_string(args[0]),
                 args[1].parse().unwrap(),
@@ -191,6 +292,16 @@ impl Instruction {
                     },
                 )
             }
+            "filter" | "pick" => Filter(FilterMetadata {
+                input: args[0].trim().to_owned(),
+                output: args[1].trim().to_owned(),
+                conditions: args
+                    .iter()
+                    .skip(2)
+                    .map(|x| BoolOperation::of_string_result(x).unwrap())
+                    .collect(),
+            }),
+            "identify" | "id" => Identify(Constructor::of_string(args[0])),
             "help" | "h" => Help,
             &_ => Single(Constructor::of_string(text)),
         }
@@ -227,6 +338,22 @@ impl fmt::Display for Instruction {
                     n, max_degree, condition
                 )
             }
+            EveryTree(n, rooted, conditions) => {
+                write!(
+                    f,
+                    "Run on every {} tree of order {} satisfying {:?}",
+                    if *rooted { "rooted" } else { "free" },
+                    n,
+                    conditions
+                )
+            }
+            CountTrees(n, conditions) => {
+                write!(
+                    f,
+                    "Count the trees of each order up to {} satisfying {:?}",
+                    n, conditions
+                )
+            }
             KitchenSink(conditions) => {
                 write!(
                     f,
@@ -265,6 +392,47 @@ impl fmt::Display for Instruction {
                     order, conditions
                 )
             }
+            Every(order, conditions) => {
+                write!(
+                    f,
+                    "Run on every graph of order {} satisfying {:?}",
+                    order, conditions
+                )
+            }
+            EveryRegular(order, degree, conditions) => {
+                write!(
+                    f,
+                    "Run on every {}-regular graph of order {} satisfying {:?}",
+                    degree, order, conditions
+                )
+            }
+            EveryBiregular(sides, conditions) => {
+                write!(
+                    f,
+                    "Run on every {} graph satisfying {:?}",
+                    sides, conditions
+                )
+            }
+            EveryPoset(order, conditions) => {
+                write!(
+                    f,
+                    "Run on every poset of order {} satisfying {:?}",
+                    order, conditions
+                )
+            }
+            EveryDigraph(order, tournament, conditions) => {
+                write!(
+                    f,
+                    "Run on every {} of order {} satisfying {:?}",
+                    if *tournament {
+                        "tournament"
+                    } else {
+                        "oriented graph"
+                    },
+                    order,
+                    conditions
+                )
+            }
             Collate(constr, reps, op) => {
                 write!(
                     f,
@@ -279,6 +447,8 @@ impl fmt::Display for Instruction {
                     constr, metadata
                 )
             }
+            Filter(metadata) => write!(f, "{}", metadata),
+            Identify(constr) => write!(f, "Identify ({}) among the named graphs", constr),
             Help => write!(f, "Print help text"),
         }
     }
@@ -309,6 +479,17 @@ impl Controller {
         }
     }
 
+    /**
+     * Whether the instruction uses stdout for its results, so anything else
+     * should be printed to stderr instead.
+     */
+    pub fn writes_to_stdout(&self) -> bool {
+        match &self.instruction {
+            Instruction::Filter(metadata) => metadata.writes_to_stdout(),
+            _ => false,
+        }
+    }
+
     pub fn operations_string(&self) -> String {
         let operations_strs: Vec<String> = self.operations.iter().map(|x| format!("{x}")).collect();
         operations_strs.join(", ")
@@ -356,11 +537,12 @@ impl Controller {
             .operations
             .iter()
             .filter_map(|x| match x {
-                Int(op) => Some(RationalOperation::OfInt(*op)),
+                Int(op) => Some(RationalOperation::OfInt(op.to_owned())),
                 Bool(op) => Some(RationalOperation::OfBool(Box::new(op.to_owned()))),
                 Rational(op) => Some(op.to_owned()),
                 Unit(_op) => None,
                 StringList(_op) => None,
+                Polynomial(_op) => None,
             })
             .collect();
         let mut rows = vec![];
@@ -553,55 +735,103 @@ impl Controller {
     }
 
     fn execute_search_all(&self, order: Order, conditions: &[BoolOperation]) {
-        let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
-        let mut degs = VertexVec::new(order, &Degree::ZERO);
-        let indexer = EdgeIndexer::new_complete(order);
-
-        let num_edges = (order.to_usize() * (order.to_usize() - 1)) / 2;
-        let mut found_graph = false;
-
-        'test_all_graphs: for code in 1..(1_u128 << num_edges) {
-            let mut i = 0;
-            let mut sta = code;
-            while sta % 2 == 0 {
-                let e = indexer.invert(i);
-                adj[e.fst()][e.snd()] = false;
-                adj[e.snd()][e.fst()] = false;
-                degs[e.fst()].decr_inplace();
-                degs[e.snd()].decr_inplace();
-                i += 1;
-                sta /= 2;
-            }
-            let e = indexer.invert(i);
-            adj[e.fst()][e.snd()] = true;
-            adj[e.snd()][e.fst()] = true;
-            degs[e.fst()].incr_inplace();
-            degs[e.snd()].incr_inplace();
-
-            // If this graph has decreasing degree sequence, then actually run it.
-            let mut is_good = true;
-            'test_degs: for (i, deg) in degs.iter().enumerate().skip(1) {
-                if *deg > degs[Vertex::of_usize(i - 1)] {
-                    is_good = false;
-                    break 'test_degs;
-                }
-            }
-
-            if is_good {
-                let g = Graph::of_matrix(adj.to_owned(), Constructor::Special);
-                let mut dossier = Dossier::new(Entity::Graph(g));
-                let mut ann_box = AnnotationsBox::new();
-                if self.do_all_conditions_hold(&mut dossier, &mut ann_box, conditions, true) {
-                    found_graph = true;
-                    break 'test_all_graphs;
-                }
-            }
-        }
+        let filter = GraphFilter::of_conditions(conditions);
+        let found_graph = generator::for_each_graph(order, &filter, &mut |g| {
+            let mut dossier = Dossier::new(Entity::Graph(g));
+            let mut ann_box = AnnotationsBox::new();
+            self.do_all_conditions_hold(&mut dossier, &mut ann_box, conditions, true)
+        });
         if !found_graph {
             println!("No graph found.");
         }
     }
 
+    /**
+     * Runs the operations on each entity produced by generate which passes
+     * every condition, and prints how many did.
+     */
+    fn execute_on_generated(
+        &self,
+        conditions: &[BoolOperation],
+        generate: impl FnOnce(&mut dyn FnMut(Entity) -> bool) -> bool,
+    ) {
+        let mut num_generated = 0;
+        let mut num_passed = 0;
+        generate(&mut |e| {
+            num_generated += 1;
+            let constructor = match &e {
+                Entity::Graph(g) => format!("{}", g.constructor),
+                Entity::Poset(p) => format!("{}", p.constructor),
+                Entity::Digraph(d) => format!("From digraph6 code {}", d.to_digraph6()),
+            };
+            let mut dossier = Dossier::new(e);
+            let mut ann_box = AnnotationsBox::new();
+            if self.do_all_conditions_hold(&mut dossier, &mut ann_box, conditions, false) {
+                print!("{}: ", constructor);
+                self.compute_and_print(&mut dossier, &mut ann_box);
+                num_passed += 1;
+            }
+            false
+        });
+        println!(
+            "{} of the {} generated satisfied {:?}",
+            num_passed, num_generated, conditions
+        );
+    }
+
+    fn execute_every(&self, order: Order, conditions: &[BoolOperation]) {
+        let filter = GraphFilter::of_conditions(conditions);
+        println!("Generating graphs of order {} with {}", order, filter);
+        self.execute_on_generated(conditions, |f| {
+            generator::for_each_graph(order, &filter, &mut |g| f(Entity::Graph(g)))
+        });
+    }
+
+    fn execute_every_regular(&self, order: Order, degree: Degree, conditions: &[BoolOperation]) {
+        let filter = GraphFilter::of_conditions(conditions);
+        println!(
+            "Generating {}-regular graphs of order {} with {}",
+            degree, order, filter
+        );
+        self.execute_on_generated(conditions, |f| {
+            generator::for_each_regular(order, degree, &filter, &mut |g| f(Entity::Graph(g)))
+        });
+    }
+
+    fn execute_every_biregular(&self, sides: Biregular, conditions: &[BoolOperation]) {
+        let filter = GraphFilter::of_conditions(conditions);
+        println!("Generating {} graphs with {}", sides, filter);
+        self.execute_on_generated(conditions, |f| {
+            generator::for_each_biregular(sides, &filter, &mut |g| f(Entity::Graph(g)))
+        });
+    }
+
+    fn execute_every_poset(&self, order: Order, conditions: &[BoolOperation]) {
+        let filter = PosetFilter::of_conditions(conditions);
+        println!("Generating posets of order {} with {}", order, filter);
+        self.execute_on_generated(conditions, |f| {
+            posets::for_each_poset(order, &filter, &mut |p| f(Entity::Poset(p)))
+        });
+    }
+
+    fn execute_every_digraph(&self, order: Order, tournament: bool, conditions: &[BoolOperation]) {
+        let filter = DigraphFilter::of_conditions(conditions);
+        let kind = if tournament {
+            "tournaments"
+        } else {
+            "oriented graphs"
+        };
+        println!("Generating {} of order {} with {}", kind, order, filter);
+        self.execute_on_generated(conditions, |f| {
+            let mut f = |d| f(Entity::Digraph(d));
+            if tournament {
+                digraphs::for_each_tournament(order, &filter, &mut f)
+            } else {
+                digraphs::for_each_oriented_graph(order, &filter, &mut f)
+            }
+        });
+    }
+
     fn execute_collate(&self, constr: &Constructor, op: &StringListOperation, reps: usize) {
         let mut vals: HashSet<String> = HashSet::new();
         let mut last_index_of_change = 0;
@@ -628,6 +858,126 @@ impl Controller {
         );
     }
 
+    /**
+     * Reads graphs in graph6, sparse6 or digraph6, one per line, and writes
+     * out those lines whose graphs satisfy all of the conditions. A tally of
+     * the values of the operations over the passing graphs is printed at
+     * the end.
+     */
+    fn execute_filter(&self, metadata: &FilterMetadata) {
+        let input: Box<dyn BufRead> = if metadata.input == "-" {
+            Box::new(BufReader::new(stdin()))
+        } else {
+            match File::open(&metadata.input) {
+                Ok(file) => Box::new(BufReader::new(file)),
+                Err(e) => panic!("Cannot open input file {} (Error: {})", metadata.input, e),
+            }
+        };
+        let mut output: Box<dyn Write> = if metadata.writes_to_stdout() {
+            Box::new(BufWriter::new(stdout()))
+        } else {
+            match File::create(&metadata.output) {
+                Ok(file) => Box::new(BufWriter::new(file)),
+                Err(e) => panic!("Cannot create output file {} (Error: {})", metadata.output, e),
+            }
+        };
+        let mut report: Box<dyn Write> = if metadata.writes_to_stdout() {
+            Box::new(stderr())
+        } else {
+            Box::new(stdout())
+        };
+
+        let start_time = SystemTime::now();
+        let mut num_read = 0;
+        let mut num_passed = 0;
+        let mut tally: HashMap<Vec<String>, usize> = HashMap::new();
+
+        for line in input.lines() {
+            let line = line.unwrap();
+            let code = line.trim();
+            if code.is_empty() {
+                continue;
+            }
+            num_read += 1;
+            let mut dossier = Dossier::new(graph6::new_entity(code));
+            let mut ann_box = AnnotationsBox::new();
+            if self.do_all_conditions_hold(&mut dossier, &mut ann_box, &metadata.conditions, false)
+            {
+                num_passed += 1;
+                writeln!(output, "{}", code).unwrap();
+                let values: Vec<String> = self
+                    .operations
+                    .iter()
+                    .map(|op| dossier.operate(&mut ann_box, op))
+                    .collect();
+                *tally.entry(values).or_insert(0) += 1;
+            }
+            if is_round_number(num_read) {
+                writeln!(report, "Read {} graphs, {} passed", num_read, num_passed).unwrap();
+            }
+        }
+        output.flush().unwrap();
+
+        if !self.operations.is_empty() {
+            let mut rows: Vec<(&Vec<String>, &usize)> = tally.iter().collect();
+            rows.sort();
+            for (values, count) in rows.iter() {
+                writeln!(
+                    report,
+                    "{}: [{}], count: {}",
+                    self.operations_string(),
+                    values.join(", "),
+                    count
+                )
+                .unwrap();
+            }
+        }
+        writeln!(
+            report,
+            "Read {} graphs, {} passed, time: {}",
+            num_read,
+            num_passed,
+            start_time.elapsed().unwrap().as_millis()
+        )
+        .unwrap();
+    }
+
+    fn execute_identify(&self, constr: &Constructor) {
+        let g = constr.new_entity().as_owned_graph();
+        let matches = catalogue::identify(&g);
+        if matches.is_empty() {
+            println!("No named graph found.");
+        } else {
+            println!("Isomorphic to:");
+            for c in matches.iter() {
+                println!("  {}", c);
+            }
+        }
+
+        let group = g.automorphism_group();
+        let orbits = group.vertex_orbits().to_canonical_component_vec();
+        let sizes = ComponentVec::<usize>::new_sizes(&orbits);
+        let mut orbit_sizes: Vec<usize> = sizes.iter().copied().filter(|x| *x > 0).collect();
+        orbit_sizes.sort();
+        println!("Automorphism group order: {}", group.order());
+        println!(
+            "{} vertex orbits, of sizes {:?}{}",
+            orbit_sizes.len(),
+            orbit_sizes,
+            if orbit_sizes.len() == 1 {
+                " (vertex-transitive)"
+            } else {
+                ""
+            }
+        );
+
+        if !self.operations.is_empty() {
+            let mut dossier = Dossier::new(Entity::Graph(g));
+            let mut ann = AnnotationsBox::new();
+            self.compute_and_print(&mut dossier, &mut ann);
+        }
+    }
+
     fn execute_until(&self, constr: &Constructor, conditions: &[BoolOperation], forever: bool) {
         fn print_success_proportions(
             conditions: &[BoolOperation],
@@ -781,11 +1131,12 @@ impl Controller {
             .operations
             .iter()
             .filter_map(|x| match x {
-                Int(op) => Some(RationalOperation::OfInt(*op)),
+                Int(op) => Some(RationalOperation::OfInt(op.to_owned())),
                 Bool(op) => Some(RationalOperation::OfBool(Box::new(op.to_owned()))),
                 Rational(op) => Some(op.to_owned()),
                 Unit(_op) => None,
                 StringList(_op) => None,
+                Polynomial(_op) => None,
             })
             .collect();
         let mut buckets: Vec<Vec<f64>> =
@@ -863,81 +1214,73 @@ impl Controller {
     }
 
     fn search_trees(&self, n: usize, condition: &BoolOperation, max_degree: &Degree) {
-        fn construct_tree(n: usize, parents: &[usize], condition: &BoolOperation) -> bool {
-            // Check all children of any node are in decreasing order of degree.
-            let mut num_children: Vec<usize> = vec![0; n];
-            for i in 0..(n - 1) {
-                num_children[parents[i]] += 1;
-            }
-            let mut is_in_order = true;
-            for i in 0..(n - 2) {
-                if parents[i] == parents[i + 1] && num_children[i + 1] < num_children[i + 2] {
-                    is_in_order = false;
-                }
+        let filter = TreeFilter {
+            max_degree: Some(max_degree.to_usize()),
+            ..Default::default()
+        };
+        let success = trees::for_each_free_tree(n, &filter, &mut |t| {
+            let mut dossier = Dossier::new(Entity::Graph(t));
+            let mut ann = AnnotationsBox::new();
+            let success = dossier.operate_bool(&mut ann, condition);
+            if success {
+                println!("Success!");
+                dossier.print_entity();
             }
-            if is_in_order {
-                // We just copy the vector of parents because fuck it, that's why.
-                let g = Constructor::RootedTree(parents.to_vec()).new_entity();
-                let mut dossier = Dossier::new(g);
-                let mut ann = AnnotationsBox::new();
-
-                let success = dossier.operate_bool(&mut ann, condition);
-                if success {
-                    println!("Success!");
-                    dossier.print_entity();
-                }
+            success
+        });
+        if success {
+            println!("Above tree found!")
+        } else {
+            println!("No tree found!")
+        }
+    }
 
-                success
-            } else {
-                false
+    fn execute_every_tree(&self, n: usize, rooted: bool, conditions: &[BoolOperation]) {
+        let filter = TreeFilter::of_conditions(conditions);
+        println!("Generating trees of order {} with {}", n, filter);
+        let mut num_trees = 0;
+        let mut num_passed = 0;
+        let mut f = |t| {
+            num_trees += 1;
+            let mut dossier = Dossier::new(Entity::Graph(t));
+            let mut ann_box = AnnotationsBox::new();
+            if self.do_all_conditions_hold(&mut dossier, &mut ann_box, conditions, false) {
+                print!("{}: ", dossier.get_constructor());
+                self.compute_and_print(&mut dossier, &mut ann_box);
+                num_passed += 1;
             }
+            false
+        };
+        if rooted {
+            trees::for_each_rooted_tree(n, &filter, &mut f);
+        } else {
+            trees::for_each_free_tree(n, &filter, &mut f);
         }
+        println!(
+            "{} of the {} trees generated satisfied {:?}",
+            num_passed, num_trees, conditions
+        );
+    }
 
-        fn search_trees_rec(
-            n: usize,
-            pos: usize,
-            min: usize,
-            parents: &mut Vec<usize>,
-            condition: &BoolOperation,
-            max_deg: usize,
-        ) -> bool {
-            if pos == 11 {
-                println!("{:?}", parents);
-                println!("{} {} {}", pos, min, max_deg);
-            }
-
-            if pos == n - 1 {
-                construct_tree(n, parents, condition)
-            } else {
-                for i in min..(pos + 1) {
-                    parents[pos] = i;
-                    // change max deg so that 0 is the weakly highest degree node.
-                    let new_max_deg = if min == 0 && i != 0 && pos < max_deg {
-                        pos
-                    } else {
-                        max_deg
-                    };
-                    let min = if pos < max_deg - 1 {
-                        i
-                    } else {
-                        i.max(parents[(pos + 2) - max_deg] + 1)
-                    };
-                    let success =
-                        search_trees_rec(n, pos + 1, min, parents, condition, new_max_deg);
-                    if success {
-                        return true;
-                    }
+    fn execute_count_trees(&self, max_n: usize, conditions: &[BoolOperation]) {
+        let filter = TreeFilter::of_conditions(conditions);
+        for n in 1..=max_n {
+            let start_time = SystemTime::now();
+            let mut count = 0;
+            trees::for_each_free_tree(n, &filter, &mut |t| {
+                let mut dossier = Dossier::new(Entity::Graph(t));
+                let mut ann_box = AnnotationsBox::new();
+                if self.do_all_conditions_hold(&mut dossier, &mut ann_box, conditions, false) {
+                    count += 1;
                 }
                 false
-            }
-        }
-
-        let mut parents = vec![0; n - 1];
-        let success = search_trees_rec(n, 0, 0, &mut parents, condition, max_degree.to_usize());
-        if success {
-            println!("Above tree found!")
-        } else {
-            println!("No tree found!")
+            });
+            println!(
+                "{}: {} trees, time: {}",
+                n,
+                count,
+                start_time.elapsed().unwrap().as_millis()
+            );
         }
     }
 
@@ -968,7 +1311,6 @@ impl Controller {
 
     fn execute_kitchen_sink(&self, conditions: &[BoolOperation], find_all: bool) {
         use Constructor::*;
-        use RawConstructor::*;
 
         let max_verts = 100;
         let mut constructors: Vec<Vec<Constructor>> = vec![vec![]; max_verts];
@@ -976,45 +1318,35 @@ impl Controller {
         fn ous(n: usize) -> Order {
             Order::of_usize(n)
         }
-        constructors[2].append(&mut vec![Raw(Complete(ous(2))), Raw(Empty(ous(2)))]);
-        constructors[3].append(&mut vec![
-            Raw(Complete(ous(3))),
-            Raw(Path(ous(3))),
-            Raw(Empty(ous(3))),
-        ]);
-
-        for verts in 4..max_verts {
-            let order = ous(verts);
-            constructors[verts].push(Raw(Complete(order)));
-            constructors[verts].push(Raw(Cyclic(order)));
-            constructors[verts].push(Raw(Path(order)));
-            constructors[verts].push(Raw(Star(order)));
-            constructors[verts].push(Raw(Empty(order)));
-            if verts % 4 == 2 && verts >= 10 {
-                constructors[verts].push(Raw(Petersen(verts / 2, 2)));
-                if verts >= 14 {
-                    constructors[verts].push(Raw(Petersen(verts / 2, 3)));
-                }
-            }
+        for (verts, constrs) in constructors.iter_mut().enumerate().skip(2) {
+            let raws = catalogue::raw_of_order(verts).into_iter().map(Raw).collect();
+            // Bigger orders are deduplicated as they are tested.
+            *constrs = if verts < 4 {
+                catalogue::distinct(raws)
+            } else {
+                raws
+            };
         }
-        constructors[6].push(Raw(Octahedron));
-        constructors[12].push(Raw(Icosahedron));
-        constructors[20].push(Raw(Dodecahedron));
 
         let mut verts = 4;
         let mut num_new_graphs = 0;
 
         'main: loop {
             let mut new_constructors: Vec<Constructor> = vec![];
-            let mut graphs: Vec<Graph> = vec![];
+            let mut canonical_forms: HashSet<String> = HashSet::new();
+            let mut distinct_constructors: Vec<Constructor> = vec![];
             for constr in constructors[verts].iter() {
-                // Actually test the graph
                 let g = constr.new_entity().as_owned_graph();
-                if self.test_sink_graph(g.clone(), conditions, find_all) && !find_all {
+                if !canonical_forms.insert(g.canonical_form()) {
+                    continue;
+                }
+                distinct_constructors.push(constr.to_owned());
+                // Actually test the graph
+                if self.test_sink_graph(g, conditions, find_all) && !find_all {
                     break 'main;
                 }
-                graphs.push(g);
             }
+            constructors[verts] = distinct_constructors;
             'decompose: for factor in 2..verts {
                 if factor * factor > verts {
                     break 'decompose;
@@ -1032,21 +1364,13 @@ impl Controller {
                                         Box::new((*c2).to_owned()),
                                     );
                                     let g = constr.new_entity().as_owned_graph();
-                                    let mut is_already_there = false;
-                                    'find_graph: for h in graphs.iter() {
-                                        if h.is_isomorphic_to(&g) {
-                                            is_already_there = true;
-                                            break 'find_graph;
-                                        }
-                                    }
-                                    if !is_already_there {
+                                    if canonical_forms.insert(g.canonical_form()) {
                                         // Actually test the graph
-                                        if self.test_sink_graph(g.clone(), conditions, find_all)
+                                        if self.test_sink_graph(g, conditions, find_all)
                                             && !find_all
                                         {
                                             break 'main;
                                         }
-                                        graphs.push(g);
                                         new_constructors.push(constr);
                                         num_new_graphs += 1;
                                         if num_new_graphs > max_new_graphs {
@@ -1123,8 +1447,46 @@ impl Controller {
         println!();
         println!("  trees(order, [bool operation], optional max degree)->[operations]");
         println!();
+        println!("  every_tree([order], [bool operations])->[operations]");
+        println!("     Run the operations on every free tree of that order passing every");
+        println!("     condition. Use every_rooted_tree for rooted trees.");
+        println!();
+        println!("  count_trees([max order], [bool operations])");
+        println!("     Count the free trees of each order passing every condition.");
+        println!();
         println!("  sink([bool operation])->[operations]");
         println!();
+        println!("  all([order], [bool operations])->[operations]");
+        println!("     Search every graph of that order, up to isomorphism, for one passing");
+        println!("     every condition.");
+        println!();
+        println!("  every([order], [bool operations])->[operations]");
+        println!("     Run the operations on every graph of that order passing every condition.");
+        println!();
+        println!("  every_regular([order], [degree], [bool operations])->[operations]");
+        println!("     As every, but only for regular graphs of that degree. Conditions such");
+        println!("     as connected or girth >= 5 are used to prune the search.");
+        println!();
+        println!("  every_biregular([left order], [left degree], [right order], [right degree],");
+        println!("                  [bool operations])->[operations]");
+        println!("     As every, but for bipartite graphs with those degrees on each side.");
+        println!();
+        println!("  every_poset([order], [bool operations])->[operations]");
+        println!("     Run the operations on every poset of that order passing every condition.");
+        println!("     connected, height <= h and twin_free are used to prune the search.");
+        println!();
+        println!("  every_tournament([order], [bool operations])->[operations]");
+        println!("     Run the operations on every tournament of that order passing every");
+        println!("     condition. Use every_oriented for oriented graphs. connected (meaning");
+        println!("     strongly connected) and min_out_deg >= d are used to prune the search.");
+        println!();
+        println!("  filter([input file], [output file], [bool operations])->[operations]");
+        println!("     Write out those graph6/sparse6/digraph6 lines passing every condition.");
+        println!("     Use - for stdin/stdout.");
+        println!();
+        println!("  identify([constructor])->[operations]");
+        println!("     List the named graphs and products isomorphic to the given graph.");
+        println!();
         println!("  help");
         println!();
     }
@@ -1138,6 +1500,8 @@ impl Controller {
             Until(constr, conditions) => self.execute_until(constr, conditions, false),
             Forever(constr, conditions) => self.execute_until(constr, conditions, true),
             SearchTrees(n, condition, max_degree) => self.search_trees(*n, condition, max_degree),
+            EveryTree(n, rooted, conditions) => self.execute_every_tree(*n, *rooted, conditions),
+            CountTrees(n, conditions) => self.execute_count_trees(*n, conditions),
             KitchenSink(conditions) => self.execute_kitchen_sink(conditions, false),
             KitchenSinkAll(conditions) => self.execute_kitchen_sink(conditions, true),
             Process(order) => self.execute_process(*order),
@@ -1147,8 +1511,19 @@ impl Controller {
                 self.execute_process_until_decreasing(*order, int_op, conditions)
             }
             SearchAll(order, conditions) => self.execute_search_all(*order, conditions),
+            Every(order, conditions) => self.execute_every(*order, conditions),
+            EveryRegular(order, degree, conditions) => {
+                self.execute_every_regular(*order, *degree, conditions)
+            }
+            EveryBiregular(sides, conditions) => self.execute_every_biregular(*sides, conditions),
+            EveryPoset(order, conditions) => self.execute_every_poset(*order, conditions),
+            EveryDigraph(order, tournament, conditions) => {
+                self.execute_every_digraph(*order, *tournament, conditions)
+            }
             Collate(constr, reps, op) => self.execute_collate(constr, op, *reps),
             LineGraph(constr, metadata) => self.execute_line_graph(constr, metadata),
+            Filter(metadata) => self.execute_filter(metadata),
+            Identify(constr) => self.execute_identify(constr),
             Help => Self::print_help(),
         }
     }
@@ -17,6 +17,7 @@ mod factorisation;
 mod girth;
 mod grabbing;
 mod grundy;
+mod hamiltonicity;
 mod hyperbolic;
 mod induced_forest;
 mod interval_coloring;
@@ -30,15 +31,21 @@ mod monotone;
 mod norine;
 mod percolate;
 mod planar;
+mod polynomials;
 mod poset_balance;
 mod pretty;
 mod seymour;
 mod signature;
+mod spectral;
 mod subgraphs;
+mod treewidth;
+mod tutte;
 mod twins;
 
 use std::collections::HashMap;
 
+use utilities::polynomial::bivariate::*;
+use utilities::polynomial::*;
 use utilities::rational::*;
 
 use crate::annotations::*;
@@ -49,6 +56,7 @@ use crate::operation::*;
 
 use crate::operation::bool_operation::*;
 use crate::operation::int_operation::*;
+use crate::operation::polynomial_operation::*;
 use crate::operation::rational_operation::*;
 use crate::operation::string_list_operation::*;
 use crate::operation::unit_operation::*;
@@ -58,6 +66,8 @@ pub struct Dossier {
     previous_int_values: HashMap<IntOperation, u32>,
     previous_bool_values: HashMap<BoolOperation, bool>,
     previous_rational_values: HashMap<RationalOperation, Rational>,
+    previous_polynomial_values: HashMap<PolynomialOperation, Polynomial>,
+    tutte: Option<BivariatePolynomial>,
 }
 
 pub struct AnnotationsBox(Option<Annotations>);
@@ -163,11 +173,29 @@ impl Dossier {
                         norine::min_antipode_distance(self.e.as_graph(), *colouring_type)
                     }
                     MinKernelSize => kernels::min_kernel_size(self.e.as_digraph(), false),
+                    MinOutDegree => self.e.as_digraph().min_out_degree().to_usize() as u32,
                     MaxCodegree => self.e.as_graph().max_codegree() as u32,
                     OneFactorSubsets => factorisation::randomly_factorise(self.e.as_graph()),
+                    NumSubgraphs(h) => {
+                        subgraphs::count_subgraphs(self.e.as_graph(), &h.graph, false)
+                    }
+                    NumInducedSubgraphs(h) => {
+                        subgraphs::count_subgraphs(self.e.as_graph(), &h.graph, true)
+                    }
+                    NumSpanningTrees => u32::try_from(self.tutte_polynomial().evaluate(1, 1))
+                        .expect("Too many spanning trees to count in a u32!"),
+                    NumSpanningForests => u32::try_from(self.tutte_polynomial().evaluate(2, 1))
+                        .expect("Too many spanning forests to count in a u32!"),
+                    NumAcyclicOrientations => u32::try_from(self.tutte_polynomial().evaluate(2, 0))
+                        .expect("Too many acyclic orientations to count in a u32!"),
+                    NumDistinctEigenvalues => spectral::num_distinct_eigenvalues(self.e.as_graph()),
+                    Circumference => hamiltonicity::circumference(self.e.as_graph()),
+                    LongestPath => hamiltonicity::longest_path(self.e.as_graph()),
+                    Treewidth => treewidth::treewidth(self.e.as_graph()),
+                    Pathwidth => treewidth::pathwidth(self.e.as_graph()),
                     Number(k) => *k,
                 };
-                self.previous_int_values.insert(*operation, value);
+                self.previous_int_values.insert(operation.to_owned(), value);
                 value
             }
         }
@@ -261,6 +289,9 @@ impl Dossier {
                         interval_coloring::has_interval_coloring(self.e.as_graph())
                     }
                     IsPlanar => planar::is_planar(self.e.as_graph()),
+                    IsHamiltonian => hamiltonicity::is_hamiltonian(self.e.as_graph()),
+                    IsTraceable => hamiltonicity::is_traceable(self.e.as_graph()),
+                    IsHamiltonConnected => hamiltonicity::is_hamilton_connected(self.e.as_graph()),
                     IsRegular => self.e.as_graph().is_regular(),
                     BunkbedDiffsAllUnimodal => bunkbed::are_all_diffs_unimodal(self.e.as_graph()),
                     HasRegularLosslessEdgeDominator => {
@@ -421,6 +452,15 @@ impl Dossier {
                     HasFewEdgesHyperbolic => {
                         hyperbolic::has_hereditarily_few_edges(self.e.as_graph())
                     }
+                    ContainsSubgraph(h) => {
+                        subgraphs::contains_subgraph(self.e.as_graph(), &h.graph, false)
+                    }
+                    ContainsInduced(h) => {
+                        subgraphs::contains_subgraph(self.e.as_graph(), &h.graph, true)
+                    }
+                    IsUnimodal(op) => self.operate_polynomial(op).are_coefs_unimodal(),
+                    IsLogConcave(op) => self.operate_polynomial(op).are_coefs_log_concave(),
+                    IsRealRooted(op) => self.operate_polynomial(op).is_real_rooted(),
                     Debug => debug::debug(self.e.as_graph()),
                 };
                 self.previous_bool_values
@@ -469,6 +509,11 @@ impl Dossier {
                     NorineAverageDistance(colouring_type) => {
                         norine::average_antipode_distance(self.e.as_graph(), *colouring_type)
                     }
+                    LargestEigenvalue => spectral::largest_eigenvalue(self.e.as_graph()),
+                    AlgebraicConnectivity => spectral::laplacian_eigenvalue(self.e.as_graph(), 1),
+                    SpectralGap => spectral::spectral_gap(self.e.as_graph()),
+                    Energy => spectral::energy(self.e.as_graph()),
+                    LaplacianEigenvalue(k) => spectral::laplacian_eigenvalue(self.e.as_graph(), *k),
                 };
                 self.previous_rational_values
                     .insert(operation.to_owned(), value);
@@ -477,6 +522,41 @@ impl Dossier {
         }
     }
 
+    pub fn operate_polynomial(&mut self, operation: &PolynomialOperation) -> Polynomial {
+        use PolynomialOperation::*;
+        match self.previous_polynomial_values.get(operation) {
+            Some(value) => value.to_owned(),
+            None => {
+                let tutte = match operation {
+                    Reliability => self.tutte_polynomial(),
+                    _ => BivariatePolynomial::new(),
+                };
+                let g = self.e.as_graph();
+                let value = match operation {
+                    Chromatic => polynomials::chromatic_polynomial(g),
+                    Independence => polynomials::independence_polynomial(g),
+                    Matching => polynomials::matching_polynomial(g),
+                    Clique => polynomials::clique_polynomial(g),
+                    Domination => polynomials::domination_polynomial(g),
+                    Reliability => tutte::reliability_polynomial(g, &tutte),
+                };
+                self.previous_polynomial_values
+                    .insert(*operation, value.to_owned());
+                value
+            }
+        }
+    }
+
+    /**
+     * The Tutte polynomial, computed once and shared by its evaluations.
+     */
+    fn tutte_polynomial(&mut self) -> BivariatePolynomial {
+        if self.tutte.is_none() {
+            self.tutte = Some(tutte::tutte_polynomial(self.e.as_graph()));
+        }
+        self.tutte.to_owned().unwrap()
+    }
+
     fn operate_unit(&mut self, ann: &mut AnnotationsBox, operation: &UnitOperation) {
         use UnitOperation::*;
         match operation {
@@ -560,6 +640,11 @@ impl Dossier {
             PrettyPrintCyclic => pretty::print_entity_cyclic(&self.e),
             PrintKozmaNitzan => kozma_nitzan::print(self.e.as_graph()),
             PrintSiteKozmaNitzan => kozma_nitzan::print_site(self.e.as_graph()),
+            PrintTutte => println!("T(x, y) = {}", self.tutte_polynomial()),
+            PrintSpectra => spectral::print_spectra(self.e.as_graph()),
+            PrintHamiltonianCycle => hamiltonicity::print_hamiltonian_cycle(self.e.as_graph()),
+            PrintTreeDecomposition => treewidth::print_tree_decomposition(self.e.as_graph()),
+            PrintPathDecomposition => treewidth::print_path_decomposition(self.e.as_graph()),
             Unit => (),
         }
     }
@@ -573,6 +658,9 @@ impl Dossier {
         match operation {
             BunkbedSiteSignatures => bunkbed_sites::signatures(self.e.as_graph()),
             Serialise => vec![self.e.as_graph().serialise()],
+            Graph6 => vec![self.e.as_graph().to_graph6()],
+            Sparse6 => vec![self.e.as_graph().to_sparse6()],
+            Digraph6 => vec![self.e.clone().as_owned_digraph().to_digraph6()],
         }
     }
 
@@ -587,6 +675,7 @@ impl Dossier {
                 "()".to_owned()
             }
             StringList(op) => format!("{:?}", self.operate_string_list(ann, op)),
+            Polynomial(op) => format!("{}", self.operate_polynomial(op)),
         }
     }
 
@@ -625,6 +714,17 @@ impl Dossier {
                 )
             }
         }
+
+        let mut polynomial_keys: Vec<&PolynomialOperation> =
+            self.previous_polynomial_values.keys().collect();
+        polynomial_keys.sort();
+        for key in polynomial_keys.iter() {
+            print!(
+                "({}: {}) ",
+                *key,
+                self.previous_polynomial_values.get(key).unwrap()
+            )
+        }
         println!();
     }
 
@@ -642,6 +742,8 @@ impl Dossier {
             previous_int_values: HashMap::new(),
             previous_bool_values: HashMap::new(),
             previous_rational_values: HashMap::new(),
+            previous_polynomial_values: HashMap::new(),
+            tutte: None,
         }
     }
 }
@@ -75,7 +75,14 @@ pub fn maker_wins_arboricity_game(g: &Graph, k: usize) -> bool {
     let indexer = EdgeIndexer::new(&g.adj_list);
     let mut maker_wins = false;
     let mut colours = EdgeVec::new(&g.adj_list, None);
-    'test_edges: for e in indexer.iter_edges() {
+    // The first move need only be tried on one edge from each orbit.
+    let first_moves: Vec<Edge> = g
+        .automorphism_group()
+        .edge_orbits(&indexer)
+        .iter()
+        .map(|orbit| orbit[0])
+        .collect();
+    'test_edges: for e in first_moves.iter() {
         colours[*e] = Some(0);
         if maker_wins_arboricity_game_rec(g, &indexer, k, 0, &mut colours, g.size(), 1) {
             maker_wins = true;
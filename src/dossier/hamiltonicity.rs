@@ -0,0 +1,408 @@
+use crate::entity::graph::*;
+
+use utilities::vertex_tools::*;
+
+// Hamiltonian cycles and paths, and more generally longest cycles and
+// paths. Small graphs use a DP over vertex sets, recording for each set S
+// which vertices can end a path that visits exactly S. Bigger graphs use a
+// backtracking search, pruned by only counting the vertices that the end of
+// the path can still reach.
+
+const MAX_DP_ORDER: usize = 16;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Start {
+    Fixed(Vertex),
+    Minimum,
+    Anywhere,
+}
+
+/**
+ * ends[S] is the set of vertices v such that some path visiting exactly the
+ * vertices of S goes from an allowed start to v. With Start::Minimum the
+ * path must start at the smallest vertex of S.
+ */
+fn path_ends(g: &Graph, start: Start) -> VertexSetVec<VertexSet> {
+    let mut ends = VertexSetVec::new(g.n, &VertexSet::new(g.n));
+    for v in g.iter_verts() {
+        let is_allowed = match start {
+            Start::Fixed(s) => v == s,
+            Start::Minimum | Start::Anywhere => true,
+        };
+        if is_allowed {
+            let singleton = VertexSet::of_vert(g.n, v);
+            ends.set(singleton, singleton);
+        }
+    }
+    for code in 1..(1_u128 << g.n.to_usize()) {
+        let set = VertexSet::of_int(code, g.n);
+        let min = set.get_first_element().unwrap();
+        for v in ends.get(set).iter() {
+            for w in g.adj_list[v].iter() {
+                if set.has_vert(*w) || start == Start::Minimum && *w < min {
+                    continue;
+                }
+                let bigger = set.add_vert_immutable(*w);
+                let mut bigger_ends = *ends.get(bigger);
+                bigger_ends.add_vert(*w);
+                ends.set(bigger, bigger_ends);
+            }
+        }
+    }
+    ends
+}
+
+/**
+ * Walks back through the DP to find the path through set ending at v.
+ */
+fn reconstruct_path(
+    g: &Graph,
+    ends: &VertexSetVec<VertexSet>,
+    set: VertexSet,
+    v: Vertex,
+) -> Vec<Vertex> {
+    let mut path = vec![v];
+    let mut set = set;
+    while set.size() > 1 {
+        let last = *path.last().unwrap();
+        set.remove_vert(last);
+        let prev = ends.get(set).iter().find(|u| g.adj[*u][last]).unwrap();
+        path.push(prev);
+    }
+    path.reverse();
+    path
+}
+
+/**
+ * Whether we want the longest path or cycle, or only one through every
+ * vertex that we are allowed to use.
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Goal {
+    Longest,
+    Spanning,
+}
+
+struct PathSearch<'a> {
+    g: &'a Graph,
+    visited: VertexVec<bool>,
+    path: Vec<Vertex>,
+    best: Vec<Vertex>,
+    is_cycle: bool,
+    end: Option<Vertex>,
+    goal: Goal,
+    num_allowed: usize,
+}
+
+impl PathSearch<'_> {
+    /**
+     * How many unvisited vertices the end of the path can still reach.
+     */
+    fn num_reachable(&self) -> usize {
+        let mut seen = self.visited.to_owned();
+        let mut stack = vec![*self.path.last().unwrap()];
+        let mut num = 0;
+        while let Some(u) = stack.pop() {
+            for w in self.g.adj_list[u].iter() {
+                if !seen[*w] {
+                    seen[*w] = true;
+                    num += 1;
+                    stack.push(*w);
+                }
+            }
+        }
+        num
+    }
+
+    /**
+     * For a spanning cycle, every unvisited vertex needs two neighbours that
+     * are either unvisited or ends of the path.
+     */
+    fn has_stranded_vertex(&self) -> bool {
+        let first = self.path[0];
+        let last = *self.path.last().unwrap();
+        self.g.iter_verts().any(|w| {
+            !self.visited[w]
+                && self.g.adj_list[w]
+                    .iter()
+                    .filter(|x| !self.visited[**x] || **x == first || **x == last)
+                    .count()
+                    < 2
+        })
+    }
+
+    fn is_valid(&self) -> bool {
+        let last = *self.path.last().unwrap();
+        let is_closed = if self.is_cycle {
+            self.path.len() >= 3 && self.g.adj[last][self.path[0]]
+        } else {
+            self.end.is_none_or(|end| last == end)
+        };
+        is_closed && (self.goal == Goal::Longest || self.path.len() == self.num_allowed)
+    }
+
+    /**
+     * Returns true once there is nothing better left to find.
+     */
+    fn search(&mut self) -> bool {
+        if self.path.len() > self.best.len() && self.is_valid() {
+            self.best = self.path.to_owned();
+            if self.best.len() == self.num_allowed {
+                return true;
+            }
+        }
+        let last = *self.path.last().unwrap();
+        let bound = self.path.len() + self.num_reachable();
+        let is_hopeless = match self.goal {
+            Goal::Longest => bound <= self.best.len(),
+            Goal::Spanning => {
+                bound < self.num_allowed || self.is_cycle && self.has_stranded_vertex()
+            }
+        };
+        if self.end == Some(last) || is_hopeless {
+            return false;
+        }
+        for i in 0..self.g.adj_list[last].len() {
+            let w = self.g.adj_list[last][i];
+            if self.visited[w] {
+                continue;
+            }
+            self.visited[w] = true;
+            self.path.push(w);
+            if self.search() {
+                return true;
+            }
+            self.path.pop();
+            self.visited[w] = false;
+        }
+        false
+    }
+}
+
+/**
+ * Searches for paths or cycles starting at start, and avoiding the
+ * forbidden vertices.
+ */
+fn search_from(
+    g: &Graph,
+    start: Vertex,
+    forbidden: impl Fn(Vertex) -> bool,
+    is_cycle: bool,
+    end: Option<Vertex>,
+    goal: Goal,
+) -> Vec<Vertex> {
+    let mut visited = VertexVec::new_fn(g.n, forbidden);
+    visited[start] = true;
+    let num_allowed = 1 + visited.iter().filter(|x| !**x).count();
+    let mut search = PathSearch {
+        g,
+        visited,
+        path: vec![start],
+        best: vec![],
+        is_cycle,
+        end,
+        goal,
+        num_allowed,
+    };
+    search.search();
+    search.best
+}
+
+/**
+ * A Hamiltonian cycle, listed as its sequence of vertices, if there is one.
+ */
+pub fn hamiltonian_cycle(g: &Graph) -> Option<Vec<Vertex>> {
+    let n = g.n.to_usize();
+    if n < 3 {
+        return None;
+    }
+    if n <= MAX_DP_ORDER {
+        let ends = path_ends(g, Start::Fixed(Vertex::ZERO));
+        let everything = VertexSet::everything(g.n);
+        ends.get(everything)
+            .iter()
+            .find(|v| g.adj[*v][Vertex::ZERO])
+            .map(|v| reconstruct_path(g, &ends, everything, v))
+    } else {
+        let cycle = search_from(g, Vertex::ZERO, |_| false, true, None, Goal::Spanning);
+        Some(cycle).filter(|cycle| !cycle.is_empty())
+    }
+}
+
+pub fn is_hamiltonian(g: &Graph) -> bool {
+    hamiltonian_cycle(g).is_some()
+}
+
+pub fn is_traceable(g: &Graph) -> bool {
+    longest_path(g) + 1 == g.n.to_usize() as u32
+}
+
+/**
+ * Is there a Hamiltonian path between every pair of vertices?
+ */
+pub fn is_hamilton_connected(g: &Graph) -> bool {
+    let n = g.n.to_usize();
+    if n <= MAX_DP_ORDER {
+        let everything = VertexSet::everything(g.n);
+        g.iter_verts().all(|u| {
+            let ends = path_ends(g, Start::Fixed(u));
+            ends.get(everything).size() == n - 1 || n == 1
+        })
+    } else {
+        g.iter_pairs().all(|(u, v)| {
+            let path = search_from(g, u, |_| false, false, Some(v), Goal::Spanning);
+            !path.is_empty()
+        })
+    }
+}
+
+/**
+ * The length of the longest cycle, or 0 for forests.
+ */
+pub fn circumference(g: &Graph) -> u32 {
+    let n = g.n.to_usize();
+    if n <= MAX_DP_ORDER {
+        let ends = path_ends(g, Start::Minimum);
+        let mut best = 0;
+        for (set, set_ends) in ends.iter_enum() {
+            let min = set.get_first_element();
+            let size = set.size();
+            if size >= 3 && size > best && set_ends.iter().any(|v| g.adj[v][min.unwrap()]) {
+                best = size;
+            }
+        }
+        best as u32
+    } else if is_hamiltonian(g) {
+        n as u32
+    } else {
+        // Each cycle is found from its smallest vertex, so later starts have
+        // fewer vertices to use.
+        let mut best = 0;
+        for (i, start) in g.iter_verts().enumerate() {
+            if best >= n - i {
+                break;
+            }
+            let cycle = search_from(g, start, |v| v < start, true, None, Goal::Longest);
+            best = best.max(cycle.len());
+        }
+        best as u32
+    }
+}
+
+/**
+ * The number of edges in the longest path.
+ */
+pub fn longest_path(g: &Graph) -> u32 {
+    let n = g.n.to_usize();
+    if n == 0 {
+        return 0;
+    }
+    let num_verts = if n <= MAX_DP_ORDER {
+        let ends = path_ends(g, Start::Anywhere);
+        ends.iter_enum()
+            .filter(|(_, set_ends)| set_ends.is_nonempty())
+            .map(|(set, _)| set.size())
+            .max()
+            .unwrap()
+    } else {
+        let mut best = 0;
+        for start in g.iter_verts() {
+            best = best.max(search_from(g, start, |_| false, false, None, Goal::Longest).len());
+            if best == n {
+                break;
+            }
+        }
+        best
+    };
+    num_verts as u32 - 1
+}
+
+pub fn print_hamiltonian_cycle(g: &Graph) {
+    match hamiltonian_cycle(g) {
+        Some(cycle) => {
+            let verts: Vec<String> = cycle.iter().map(|v| v.to_string()).collect();
+            println!("Hamiltonian cycle: {}", verts.join(" -> "));
+        }
+        None => println!("No Hamiltonian cycle!"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    fn is_cycle_of(g: &Graph, cycle: &[Vertex]) -> bool {
+        let mut seen = VertexVec::new(g.n, &false);
+        for v in cycle.iter() {
+            seen[*v] = true;
+        }
+        seen.iter().all(|x| *x)
+            && cycle.len() == g.n.to_usize()
+            && (0..cycle.len()).all(|i| g.adj[cycle[i]][cycle[(i + 1) % cycle.len()]])
+    }
+
+    #[test]
+    fn test_hamiltonian_small() {
+        let petersen = graph("petersen");
+        assert!(!is_hamiltonian(&petersen));
+        assert!(is_traceable(&petersen));
+        assert_eq!(circumference(&petersen), 9);
+        assert_eq!(longest_path(&petersen), 9);
+        assert!(!is_traceable(&graph("k(1,3)")));
+        assert_eq!(longest_path(&graph("k(1,3)")), 2);
+        assert_eq!(circumference(&graph("p(6)")), 0);
+        assert!(!is_hamiltonian(&graph("k(3,4)")));
+        assert_eq!(circumference(&graph("k(3,4)")), 6);
+        let cube = graph("q(3)");
+        assert!(is_cycle_of(&cube, &hamiltonian_cycle(&cube).unwrap()));
+    }
+
+    #[test]
+    fn test_hamilton_connected() {
+        assert!(is_hamilton_connected(&graph("k(5)")));
+        // Bipartite graphs have no Hamiltonian paths between vertices on
+        // the same side.
+        assert!(!is_hamilton_connected(&graph("q(3)")));
+        assert!(!is_hamilton_connected(&graph("c(5)")));
+    }
+
+    #[test]
+    fn test_hamiltonian_backtracking() {
+        // Random Hamiltonian graphs too big for the DP.
+        for _ in 0..5 {
+            let g = graph("hpm(30,3)");
+            assert!(is_cycle_of(&g, &hamiltonian_cycle(&g).unwrap()));
+            assert!(is_traceable(&g));
+            assert_eq!(circumference(&g), 30);
+        }
+        // Subdividing the Petersen graph gives a bipartite graph with sides
+        // of sizes 10 and 15, whose cycles come from those of the Petersen
+        // graph.
+        let subdivided = graph("subdivide(petersen)");
+        assert!(!is_hamiltonian(&subdivided));
+        assert!(!is_traceable(&subdivided));
+        assert_eq!(circumference(&subdivided), 18);
+        assert!(!is_hamilton_connected(&graph("c(17)")));
+        assert!(is_hamilton_connected(&graph("k(17)")));
+    }
+
+    #[test]
+    fn test_dp_matches_backtracking() {
+        for _ in 0..10 {
+            let g = graph("erdos_renyi(12,0.3)");
+            let mut best = 0;
+            for start in g.iter_verts() {
+                let cycle = search_from(&g, start, |v| v < start, true, None, Goal::Longest);
+                best = best.max(cycle.len());
+            }
+            assert_eq!(best as u32, circumference(&g));
+            let mut best = 0;
+            for start in g.iter_verts() {
+                best =
+                    best.max(search_from(&g, start, |_| false, false, None, Goal::Longest).len());
+            }
+            assert_eq!(best.max(1) as u32 - 1, longest_path(&g));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use crate::entity::graph::*;
+
+use utilities::polynomial::*;
+
+// Graph polynomials, all computed by brute force over subsets of vertices
+// stored as bitmasks, so these are only for small graphs.
+
+const MAX_ORDER: usize = 24;
+
+/**
+ * The chromatic polynomial takes O(3^n) time, and its coefficients stop
+ * fitting in an i64 just above this.
+ */
+const MAX_CHROMATIC_ORDER: usize = 20;
+
+fn nbhd_masks(g: &Graph) -> Vec<u64> {
+    let n = g.n.to_usize();
+    if n > MAX_ORDER {
+        panic!(
+            "Graph polynomials are only for graphs of order at most {}!",
+            MAX_ORDER
+        );
+    }
+    g.adj
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, is_adj)| **is_adj)
+                .fold(0, |mask, (j, _)| mask | (1 << j))
+        })
+        .collect()
+}
+
+/**
+ * is_independent[S] says whether the set S has no edges inside it.
+ */
+fn independent_masks(nbhds: &[u64]) -> Vec<bool> {
+    let mut is_independent = vec![true; 1 << nbhds.len()];
+    for set in 1..is_independent.len() {
+        let v = set.trailing_zeros() as usize;
+        let rest = set & (set - 1);
+        is_independent[set] = is_independent[rest] && nbhds[v] & (rest as u64) == 0;
+    }
+    is_independent
+}
+
+fn poly_of_counts(counts: &[u64]) -> Polynomial {
+    let coefs: Vec<i64> = counts.iter().map(|x| *x as i64).collect();
+    Polynomial::of_vec(&coefs).with_var_name("x")
+}
+
+fn count_by_size(n: usize, is_counted: impl Fn(usize) -> bool) -> Polynomial {
+    let mut counts = vec![0; n + 1];
+    for set in 0..(1 << n) {
+        if is_counted(set) {
+            counts[(set as u64).count_ones() as usize] += 1;
+        }
+    }
+    poly_of_counts(&counts)
+}
+
+/**
+ * The sum of x^|S| over independent sets S.
+ */
+pub fn independence_polynomial(g: &Graph) -> Polynomial {
+    let is_independent = independent_masks(&nbhd_masks(g));
+    count_by_size(g.n.to_usize(), |set| is_independent[set])
+}
+
+/**
+ * The sum of x^|S| over cliques S, including the empty one.
+ */
+pub fn clique_polynomial(g: &Graph) -> Polynomial {
+    independence_polynomial(&g.complement())
+}
+
+/**
+ * The sum of x^|S| over dominating sets S.
+ */
+pub fn domination_polynomial(g: &Graph) -> Polynomial {
+    let n = g.n.to_usize();
+    let closed_nbhds: Vec<u64> = nbhd_masks(g)
+        .iter()
+        .enumerate()
+        .map(|(v, nbhd)| nbhd | (1 << v))
+        .collect();
+    let everything: u64 = (1 << n) - 1;
+    count_by_size(n, |set| {
+        let dominated = closed_nbhds
+            .iter()
+            .enumerate()
+            .filter(|(v, _)| set & (1 << v) != 0)
+            .fold(0, |mask, (_, nbhd)| mask | nbhd);
+        dominated == everything
+    })
+}
+
+fn count_matchings(nbhds: &[u64], remaining: u64, memo: &mut HashMap<u64, Vec<u64>>) -> Vec<u64> {
+    if remaining == 0 {
+        return vec![1];
+    }
+    if let Some(counts) = memo.get(&remaining) {
+        return counts.to_owned();
+    }
+    // Either the lowest remaining vertex is unmatched, or it is matched to
+    // one of its remaining neighbours.
+    let v = remaining.trailing_zeros() as usize;
+    let rest = remaining & !(1 << v);
+    let mut counts = count_matchings(nbhds, rest, memo);
+    let mut nbrs = nbhds[v] & rest;
+    while nbrs != 0 {
+        let u = nbrs.trailing_zeros() as usize;
+        nbrs &= nbrs - 1;
+        for (k, c) in count_matchings(nbhds, rest & !(1 << u), memo)
+            .iter()
+            .enumerate()
+        {
+            if k + 1 >= counts.len() {
+                counts.push(0);
+            }
+            counts[k + 1] += c;
+        }
+    }
+    memo.insert(remaining, counts.to_owned());
+    counts
+}
+
+/**
+ * The sum of x^|M| over matchings M. This is the generating function
+ * version, which is real-rooted exactly when the usual matching polynomial
+ * sum (-1)^k m_k x^(n - 2k) is.
+ */
+pub fn matching_polynomial(g: &Graph) -> Polynomial {
+    let nbhds = nbhd_masks(g);
+    let everything: u64 = (1 << nbhds.len()) - 1;
+    poly_of_counts(&count_matchings(&nbhds, everything, &mut HashMap::new()))
+}
+
+/**
+ * We count the partitions of the vertices into exactly k independent sets
+ * for each k, and then the chromatic polynomial is the sum of these counts
+ * times the falling factorials x(x - 1)...(x - k + 1). Takes O(3^n) time.
+ */
+pub fn chromatic_polynomial(g: &Graph) -> Polynomial {
+    let n = g.n.to_usize();
+    if n > MAX_CHROMATIC_ORDER {
+        panic!(
+            "Chromatic polynomials are only for graphs of order at most {}!",
+            MAX_CHROMATIC_ORDER
+        );
+    }
+    let is_independent = independent_masks(&nbhd_masks(g));
+    // num_partitions[S][k] is the number of partitions of S into k parts.
+    let mut num_partitions: Vec<Vec<u64>> = vec![vec![1]];
+    for set in 1..(1_usize << n) {
+        let lowest = set & set.wrapping_neg();
+        let rest = set ^ lowest;
+        let mut counts = vec![0; (set.count_ones() + 1) as usize];
+        // The part containing the lowest vertex is lowest | sub.
+        let mut sub = rest;
+        loop {
+            let part = lowest | sub;
+            if is_independent[part] {
+                for (k, c) in num_partitions[set ^ part].iter().enumerate() {
+                    counts[k + 1] += c;
+                }
+            }
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & rest;
+        }
+        num_partitions.push(counts);
+    }
+
+    // The terms can be much bigger than the coefficients they add up to.
+    fn overflow<T>() -> T {
+        panic!("Overflow in the chromatic polynomial!")
+    }
+    let mut coefs: Vec<i128> = vec![0; n + 1];
+    let mut falling_factorial: Vec<i128> = vec![1];
+    for (k, c) in num_partitions[(1 << n) - 1].iter().enumerate() {
+        for (coef, x) in coefs.iter_mut().zip(falling_factorial.iter()) {
+            *coef = x
+                .checked_mul(*c as i128)
+                .and_then(|term| coef.checked_add(term))
+                .unwrap_or_else(overflow);
+        }
+        // Multiply the falling factorial by x - k.
+        let mut next = vec![0; falling_factorial.len() + 1];
+        for (i, x) in falling_factorial.iter().enumerate() {
+            next[i + 1] += x;
+            next[i] = x
+                .checked_mul(k as i128)
+                .and_then(|term| next[i].checked_sub(term))
+                .unwrap_or_else(overflow);
+        }
+        falling_factorial = next;
+    }
+    let coefs: Vec<i64> = coefs
+        .iter()
+        .map(|x| i64::try_from(*x).unwrap_or_else(|_| overflow()))
+        .collect();
+    Polynomial::of_vec(&coefs).with_var_name("x")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    fn coefs(text: &str, poly: impl Fn(&Graph) -> Polynomial) -> Vec<i64> {
+        let mut coefs = poly(&graph(text)).coefs().to_owned();
+        while coefs.last() == Some(&0) {
+            coefs.pop();
+        }
+        coefs
+    }
+
+    #[test]
+    fn test_chromatic_polynomial() {
+        // x(x - 1)(x - 2) and (x - 1)^4 + (x - 1) for C_4.
+        assert_eq!(coefs("k(3)", chromatic_polynomial), vec![0, 2, -3, 1]);
+        assert_eq!(coefs("c(4)", chromatic_polynomial), vec![0, -3, 6, -4, 1]);
+        assert_eq!(coefs("e(3)", chromatic_polynomial), vec![0, 0, 0, 1]);
+        let petersen = chromatic_polynomial(&graph("petersen"));
+        assert_eq!(petersen.evaluate(2.0), 0.0);
+        assert_eq!(petersen.evaluate(3.0), 120.0);
+        // x(x - 1)...(x - 19) has the biggest coefficients allowed.
+        let k20 = chromatic_polynomial(&graph("k(20)"));
+        assert_eq!(k20.coefs()[1], -121645100408832000);
+        assert_eq!(k20.coefs()[19], -190);
+        assert_eq!(k20.coefs().iter().sum::<i64>(), 0);
+    }
+
+    #[test]
+    fn test_counting_polynomials() {
+        assert_eq!(coefs("c(5)", independence_polynomial), vec![1, 5, 5]);
+        assert_eq!(coefs("k(4)", clique_polynomial), vec![1, 4, 6, 4, 1]);
+        assert_eq!(coefs("p(4)", matching_polynomial), vec![1, 3, 1]);
+        assert_eq!(coefs("k(4)", matching_polynomial), vec![1, 6, 3]);
+        // Dominating sets of P_3: the middle vertex, or at least two.
+        assert_eq!(coefs("p(3)", domination_polynomial), vec![0, 1, 3, 1]);
+    }
+
+    #[test]
+    fn test_polynomial_properties() {
+        for text in ["petersen", "c(7)", "k(3,4)"] {
+            let g = graph(text);
+            assert!(matching_polynomial(&g).is_real_rooted());
+            assert!(matching_polynomial(&g).are_coefs_log_concave());
+            assert!(chromatic_polynomial(&g).are_coefs_unimodal());
+        }
+        // Claw-free graphs have real-rooted independence polynomials, but
+        // K_{1,3} itself has 1 + 4x + 3x^2 + x^3, which does not.
+        assert!(independence_polynomial(&graph("c(6)")).is_real_rooted());
+        assert!(!independence_polynomial(&graph("k(1,3)")).is_real_rooted());
+        // The chromatic polynomial of C_4 has roots 0, 1 and two complex ones.
+        assert!(!chromatic_polynomial(&graph("c(4)")).is_real_rooted());
+        assert!(chromatic_polynomial(&graph("k(4)")).is_real_rooted());
+        // These have coefficients far too big for i128 pseudo-remainders.
+        assert!(!chromatic_polynomial(&graph("grid(3,4)")).is_real_rooted());
+        assert!(!chromatic_polynomial(&graph("k(6,6)")).is_real_rooted());
+        assert!(chromatic_polynomial(&graph("k(12)")).is_real_rooted());
+        assert!(matching_polynomial(&graph("q(4)")).is_real_rooted());
+        let flat_start = Polynomial::of_vec(&vec![1, 1, 3]);
+        assert!(flat_start.are_coefs_unimodal() && !flat_start.are_coefs_log_concave());
+        assert!(!Polynomial::of_vec(&vec![3, 1, 2]).are_coefs_unimodal());
+    }
+}
@@ -117,6 +117,25 @@ impl Embedding {
         }
     }
 
+    /**
+     * Uses the positions the graph was built with, if it has any.
+     */
+    pub fn new_of_positions(g: &Graph) -> Option<Self> {
+        let positions = g
+            .positions
+            .as_ref()?
+            .iter()
+            .map(|(x, y)| Point { x: *x, y: *y })
+            .collect();
+        Some(Self {
+            positions,
+            delta: 0.0,
+            repulsion_scale: 0.0,
+            edge_repulsion: 0.0,
+            edge_repulsion_threshold: 0.0,
+        })
+    }
+
     fn get_attraction(&self, u: Vertex, v: Vertex, is_edge: bool) -> f64 {
         let dist = (self.get(u) - self.get(v)).length();
         let repulsion = self.repulsion_scale / (dist * dist);
@@ -416,6 +435,9 @@ impl Printer {
 }
 
 fn get_optimal_embedding(g: &Graph, rng: &mut ThreadRng) -> Embedding {
+    if let Some(embedding) = Embedding::new_of_positions(g) {
+        return embedding;
+    }
     let mut optimal_embedding = Embedding::new(g, 0.5, 0.05, rng);
     let mut min_energy = f64::MAX;
 
@@ -0,0 +1,189 @@
+use crate::entity::graph::*;
+
+use utilities::linear_algebra::*;
+use utilities::rational::*;
+
+// Eigenvalues of the adjacency matrix A, the Laplacian D - A and the
+// signless Laplacian D + A. These are floating point, and are turned into
+// rationals with small denominators when they are reported as invariants.
+
+const MAX_DENOMINATOR: u64 = 1_000_000;
+
+/**
+ * Eigenvalues closer than this are treated as equal.
+ */
+const EIGENVALUE_TOLERANCE: f64 = 1e-6;
+
+pub fn rational_of_f64(x: f64) -> Rational {
+    Rational::approximate(x, MAX_DENOMINATOR)
+}
+
+/**
+ * The matrix degree_coef D + adj_coef A.
+ */
+fn matrix(g: &Graph, degree_coef: f64, adj_coef: f64) -> Vec<Vec<f64>> {
+    g.iter_verts()
+        .map(|u| {
+            g.iter_verts()
+                .map(|v| {
+                    if u == v {
+                        degree_coef * g.deg[u].to_usize() as f64
+                    } else if g.adj[u][v] {
+                        adj_coef
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn adjacency_spectrum(g: &Graph) -> Vec<f64> {
+    symmetric_eigenvalues(&matrix(g, 0.0, 1.0))
+}
+
+pub fn laplacian_spectrum(g: &Graph) -> Vec<f64> {
+    symmetric_eigenvalues(&matrix(g, 1.0, -1.0))
+}
+
+pub fn signless_laplacian_spectrum(g: &Graph) -> Vec<f64> {
+    symmetric_eigenvalues(&matrix(g, 1.0, 1.0))
+}
+
+pub fn largest_eigenvalue(g: &Graph) -> Rational {
+    rational_of_f64(*adjacency_spectrum(g).last().unwrap())
+}
+
+/**
+ * The difference between the two largest adjacency eigenvalues.
+ */
+pub fn spectral_gap(g: &Graph) -> Rational {
+    let spectrum = adjacency_spectrum(g);
+    let n = spectrum.len();
+    if n < 2 {
+        panic!("The spectral gap needs at least two vertices!");
+    }
+    rational_of_f64(spectrum[n - 1] - spectrum[n - 2])
+}
+
+/**
+ * The sum of the absolute values of the adjacency eigenvalues.
+ */
+pub fn energy(g: &Graph) -> Rational {
+    rational_of_f64(adjacency_spectrum(g).iter().map(|x| x.abs()).sum())
+}
+
+pub fn num_distinct_eigenvalues(g: &Graph) -> u32 {
+    let spectrum = adjacency_spectrum(g);
+    let mut num = 0;
+    for (i, x) in spectrum.iter().enumerate() {
+        if i == 0 || x - spectrum[i - 1] > EIGENVALUE_TOLERANCE {
+            num += 1;
+        }
+    }
+    num
+}
+
+/**
+ * The k-th smallest Laplacian eigenvalue, counting from 0. The one with
+ * k = 1 is the algebraic connectivity, which is positive exactly when the
+ * graph is connected.
+ */
+pub fn laplacian_eigenvalue(g: &Graph, k: usize) -> Rational {
+    let spectrum = laplacian_spectrum(g);
+    if k >= spectrum.len() {
+        panic!("There are only {} Laplacian eigenvalues!", spectrum.len());
+    }
+    rational_of_f64(spectrum[k])
+}
+
+fn print_spectrum(name: &str, spectrum: &[f64]) {
+    let mut groups: Vec<(f64, usize)> = vec![];
+    for x in spectrum.iter().rev() {
+        // Avoid printing -0.0000 for eigenvalues that should be zero.
+        let x = if x.abs() <= EIGENVALUE_TOLERANCE {
+            0.0
+        } else {
+            *x
+        };
+        match groups.last_mut() {
+            Some((y, mult)) if *y - x <= EIGENVALUE_TOLERANCE => *mult += 1,
+            _ => groups.push((x, 1)),
+        }
+    }
+    let terms: Vec<String> = groups
+        .iter()
+        .map(|(x, mult)| format!("{:.4}^{}", x, mult))
+        .collect();
+    println!("{}: {}", name, terms.join(", "));
+}
+
+pub fn print_spectra(g: &Graph) {
+    print_spectrum("Adjacency", &adjacency_spectrum(g));
+    print_spectrum("Laplacian", &laplacian_spectrum(g));
+    print_spectrum("Signless Laplacian", &signless_laplacian_spectrum(g));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    fn assert_close(spectrum: &[f64], expected: &[f64]) {
+        assert_eq!(spectrum.len(), expected.len());
+        for (x, y) in spectrum.iter().zip(expected.iter()) {
+            assert!((x - y).abs() < 1e-9, "{:?} vs {:?}", spectrum, expected);
+        }
+    }
+
+    #[test]
+    fn test_spectra() {
+        assert_close(
+            &adjacency_spectrum(&graph("k(4)")),
+            &[-1.0, -1.0, -1.0, 3.0],
+        );
+        assert_close(&laplacian_spectrum(&graph("k(4)")), &[0.0, 4.0, 4.0, 4.0]);
+        // The star K_{1,3} has adjacency eigenvalues +-sqrt(3) and 0, 0.
+        let root3 = 3.0_f64.sqrt();
+        assert_close(
+            &adjacency_spectrum(&graph("k(1,3)")),
+            &[-root3, 0.0, 0.0, root3],
+        );
+        // Bipartite graphs have the same Laplacian and signless spectra.
+        let cube = graph("q(3)");
+        assert_close(
+            &signless_laplacian_spectrum(&cube),
+            &laplacian_spectrum(&cube),
+        );
+    }
+
+    #[test]
+    fn test_spectral_invariants() {
+        // The Petersen graph has eigenvalues 3, 1^5 and (-2)^4.
+        let petersen = graph("petersen");
+        assert_eq!(largest_eigenvalue(&petersen), Rational::new(3));
+        assert_eq!(spectral_gap(&petersen), Rational::new(2));
+        assert_eq!(energy(&petersen), Rational::new(16));
+        assert_eq!(num_distinct_eigenvalues(&petersen), 3);
+        assert_eq!(laplacian_eigenvalue(&petersen, 1), Rational::new(2));
+        // The algebraic connectivity of C_n is 2 - 2 cos(2 pi / n).
+        let c6 = graph("c(6)");
+        assert_eq!(laplacian_eigenvalue(&c6, 1), Rational::ONE);
+        assert_eq!(
+            laplacian_eigenvalue(&graph("union(k(3),k(3))"), 1),
+            Rational::ZERO
+        );
+    }
+
+    #[test]
+    fn test_matrix_tree_theorem() {
+        // The number of spanning trees is the product of the nonzero
+        // Laplacian eigenvalues divided by n.
+        for (text, num_trees) in [("petersen", 2000.0), ("k(5)", 125.0), ("q(3)", 384.0)] {
+            let spectrum = laplacian_spectrum(&graph(text));
+            let product: f64 = spectrum.iter().skip(1).product();
+            assert!((product / spectrum.len() as f64 - num_trees).abs() < 1e-6);
+        }
+    }
+}
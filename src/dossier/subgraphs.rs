@@ -1,5 +1,7 @@
 use crate::entity::graph::*;
 
+use utilities::vertex_tools::*;
+
 pub fn is_triangle_free(g: &Graph) -> bool {
     let mut is_triangle_free = true;
 
@@ -16,3 +18,152 @@ pub fn is_triangle_free(g: &Graph) -> bool {
 
     is_triangle_free
 }
+
+/**
+ * A VF2-style search for embeddings of the pattern h into the host g.
+ * The vertices of h are matched in a fixed order, in which each vertex
+ * has as many earlier neighbours as possible. A vertex with an earlier
+ * neighbour only needs to be tried against the neighbours of its image.
+ */
+struct SubgraphMatcher<'a> {
+    g: &'a Graph,
+    h: &'a Graph,
+    induced: bool,
+    order: Vec<Vertex>,
+    anchors: Vec<Option<Vertex>>,
+    image: VertexVec<Option<Vertex>>,
+    used: VertexVec<bool>,
+}
+
+impl<'a> SubgraphMatcher<'a> {
+    fn new(g: &'a Graph, h: &'a Graph, induced: bool) -> Self {
+        let mut order: Vec<Vertex> = vec![];
+        let mut anchors: Vec<Option<Vertex>> = vec![];
+        let mut is_ordered = VertexVec::new(h.n, &false);
+        let mut num_ordered_nbrs = VertexVec::new(h.n, &0);
+        while order.len() < h.n.to_usize() {
+            let next = h
+                .iter_verts()
+                .filter(|u| !is_ordered[*u])
+                .max_by_key(|u| (num_ordered_nbrs[*u], h.deg[*u]))
+                .unwrap();
+            anchors.push(h.adj_list[next].iter().find(|u| is_ordered[**u]).copied());
+            order.push(next);
+            is_ordered[next] = true;
+            for u in h.adj_list[next].iter() {
+                num_ordered_nbrs[*u] += 1;
+            }
+        }
+        Self {
+            g,
+            h,
+            induced,
+            order,
+            anchors,
+            image: VertexVec::new(h.n, &None),
+            used: VertexVec::new(g.n, &false),
+        }
+    }
+
+    fn is_feasible(&self, depth: usize, u: Vertex, v: Vertex) -> bool {
+        if self.used[v] || self.g.deg[v] < self.h.deg[u] {
+            return false;
+        }
+        self.order[..depth].iter().all(|w| {
+            let x = self.image[*w].unwrap();
+            if self.h.adj[u][*w] {
+                self.g.adj[v][x]
+            } else {
+                !self.induced || !self.g.adj[v][x]
+            }
+        })
+    }
+
+    /**
+     * Counts the embeddings extending the current partial one, stopping
+     * as soon as one is found if stop_at_first is set.
+     */
+    fn search(&mut self, depth: usize, stop_at_first: bool) -> u64 {
+        if depth == self.order.len() {
+            return 1;
+        }
+        let u = self.order[depth];
+        let candidates: Vec<Vertex> = match self.anchors[depth] {
+            Some(w) => self.g.adj_list[self.image[w].unwrap()].to_owned(),
+            None => self.g.iter_verts().collect(),
+        };
+        let mut count = 0;
+        for v in candidates.into_iter() {
+            if !self.is_feasible(depth, u, v) {
+                continue;
+            }
+            self.image[u] = Some(v);
+            self.used[v] = true;
+            count += self.search(depth + 1, stop_at_first);
+            self.image[u] = None;
+            self.used[v] = false;
+            if stop_at_first && count > 0 {
+                break;
+            }
+        }
+        count
+    }
+}
+
+/**
+ * Does g contain a copy of h, or an induced copy if induced is set?
+ */
+pub fn contains_subgraph(g: &Graph, h: &Graph, induced: bool) -> bool {
+    h.n <= g.n && SubgraphMatcher::new(g, h, induced).search(0, true) > 0
+}
+
+/**
+ * The number of (induced) subgraphs of g isomorphic to h. Each of them is
+ * hit by exactly |Aut(h)| embeddings of h.
+ */
+pub fn count_subgraphs(g: &Graph, h: &Graph, induced: bool) -> u32 {
+    if h.n > g.n {
+        return 0;
+    }
+    let embeddings = SubgraphMatcher::new(g, h, induced).search(0, false);
+    u32::try_from(embeddings / h.automorphism_group().order() as u64)
+        .expect("Too many subgraphs to count in a u32!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::*;
+
+    #[test]
+    fn test_containment() {
+        let petersen = graph("petersen");
+        assert!(contains_subgraph(&petersen, &graph("c(5)"), true));
+        assert!(contains_subgraph(&petersen, &graph("c(9)"), false));
+        assert!(!contains_subgraph(&petersen, &graph("c(4)"), false));
+        assert!(!contains_subgraph(&petersen, &graph("k(3)"), false));
+        assert!(contains_subgraph(&petersen, &graph("star(4)"), true));
+
+        let q3 = graph("q(3)");
+        assert!(contains_subgraph(&q3, &graph("p(4)"), true));
+        assert!(contains_subgraph(&q3, &graph("c(6)"), true));
+        assert!(contains_subgraph(&q3, &graph("c(8)"), false));
+        assert!(!contains_subgraph(&q3, &graph("c(8)"), true));
+    }
+
+    #[test]
+    fn test_counting() {
+        assert_eq!(count_subgraphs(&graph("k(5)"), &graph("k(3)"), false), 10);
+        assert_eq!(count_subgraphs(&graph("k(4)"), &graph("c(4)"), false), 3);
+        assert_eq!(count_subgraphs(&graph("k(4)"), &graph("c(4)"), true), 0);
+        assert_eq!(
+            count_subgraphs(&graph("petersen"), &graph("c(5)"), false),
+            12
+        );
+        assert_eq!(count_subgraphs(&graph("q(3)"), &graph("c(4)"), true), 6);
+        assert_eq!(
+            count_subgraphs(&graph("petersen"), &graph("star(4)"), true),
+            10
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use crate::entity::graph::*;
+use crate::entity::tree_decomposition::*;
+
+use utilities::vertex_tools::*;
+use utilities::*;
+
+// Treewidth and pathwidth as minimax problems over vertex orderings. For
+// treewidth, each vertex costs the number of later vertices it reaches
+// through earlier ones, which are its neighbours when it is eliminated. For
+// pathwidth, it costs the number of earlier vertices that still have later
+// neighbours. Either way the cost only depends on the set of earlier
+// vertices, so small graphs get a DP over vertex sets, and bigger ones a
+// branch and bound search which remembers the best width with which it has
+// reached each set.
+
+const MAX_DP_ORDER: usize = 18;
+
+/**
+ * The largest order for which VertexSet::everything fits in a u128.
+ */
+const MAX_ORDER: usize = 127;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Width {
+    Tree,
+    Path,
+}
+
+struct OrderingSearch {
+    n: Order,
+    nbhds: VertexVec<VertexSet>,
+    width: Width,
+    lower_bound: usize,
+    best_width: usize,
+    best_ordering: Vec<Vertex>,
+    reached: HashMap<VertexSet, usize>,
+}
+
+impl OrderingSearch {
+    fn new(g: &Graph, width: Width) -> OrderingSearch {
+        if g.n.to_usize() > MAX_ORDER {
+            panic!("Widths are only for graphs of order at most {}!", MAX_ORDER);
+        }
+        let mut search = OrderingSearch {
+            n: g.n,
+            nbhds: g
+                .adj_list
+                .iter()
+                .map(|nbrs| VertexSet::of_vec(g.n, nbrs))
+                .collect(),
+            width,
+            lower_bound: 0,
+            best_width: usize::MAX,
+            best_ordering: vec![],
+            reached: HashMap::new(),
+        };
+        search.lower_bound = search.degeneracy();
+        search
+    }
+
+    /**
+     * Every subgraph has a vertex of degree at most the treewidth, so the
+     * largest degree met while removing minimum degree vertices one at a
+     * time is a lower bound for both widths.
+     */
+    fn degeneracy(&self) -> usize {
+        let mut remaining = VertexSet::everything(self.n);
+        let mut bound = 0;
+        while remaining.is_nonempty() {
+            let (deg, v) = remaining
+                .iter()
+                .map(|v| (self.nbhds[v].inter(&remaining).size(), v))
+                .min()
+                .unwrap();
+            bound = bound.max(deg);
+            remaining.remove_vert(v);
+        }
+        bound
+    }
+
+    /**
+     * The vertices outside before which v reaches through before.
+     */
+    fn eliminated_nbhd(&self, before: VertexSet, v: Vertex) -> VertexSet {
+        let mut inside = VertexSet::of_vert(self.n, v);
+        let mut frontier = inside;
+        let mut nbhd = VertexSet::new(self.n);
+        while frontier.is_nonempty() {
+            let mut next = VertexSet::new(self.n);
+            for u in frontier.iter() {
+                next.add_all(self.nbhds[u]);
+            }
+            nbhd.add_all(next);
+            frontier = next.inter(&before).setminus(inside);
+            inside.add_all(frontier);
+        }
+        nbhd.setminus(inside).setminus(before)
+    }
+
+    /**
+     * The number of other vertices in the bag of v, when v comes straight
+     * after the vertices in before.
+     */
+    fn cost(&self, before: VertexSet, v: Vertex) -> usize {
+        match self.width {
+            Width::Tree => self.eliminated_nbhd(before, v).size(),
+            Width::Path => before
+                .iter()
+                .filter(|u| self.nbhds[*u].setminus(before).is_nonempty())
+                .count(),
+        }
+    }
+
+    fn dp(&mut self) {
+        let n = self.n.to_usize();
+        let mut width = VertexSetVec::new(self.n, &0);
+        let mut last = VertexSetVec::new(self.n, &Vertex::ZERO);
+        for code in 1..(1_u128 << n) {
+            let set = VertexSet::of_int(code, self.n);
+            let (best, v) = set
+                .iter()
+                .map(|v| {
+                    let before = set.remove_vert_immutable(v);
+                    (self.cost(before, v).max(*width.get(before)), v)
+                })
+                .min()
+                .unwrap();
+            width.set(set, best);
+            last.set(set, v);
+        }
+        let mut set = VertexSet::everything(self.n);
+        let mut ordering = vec![];
+        while set.is_nonempty() {
+            let v = *last.get(set);
+            ordering.push(v);
+            set.remove_vert(v);
+        }
+        ordering.reverse();
+        self.best_width = *width.get(VertexSet::everything(self.n));
+        self.best_ordering = ordering;
+    }
+
+    /**
+     * Takes the cheapest vertex each time, which for treewidth is the
+     * minimum degree heuristic.
+     */
+    fn greedy(&mut self) {
+        let mut before = VertexSet::new(self.n);
+        let mut ordering = vec![];
+        let mut max_cost = 0;
+        for _ in 0..self.n.to_usize() {
+            let (cost, v) = before
+                .not()
+                .iter()
+                .map(|v| (self.cost(before, v), v))
+                .min()
+                .unwrap();
+            max_cost = max_cost.max(cost);
+            ordering.push(v);
+            before.add_vert(v);
+        }
+        self.best_width = max_cost;
+        self.best_ordering = ordering;
+    }
+
+    /**
+     * Returns true once the lower bound has been achieved.
+     */
+    fn branch(&mut self, before: VertexSet, ordering: &mut Vec<Vertex>, width: usize) -> bool {
+        if width >= self.best_width {
+            return false;
+        }
+        if ordering.len() == self.n.to_usize() {
+            self.best_width = width;
+            self.best_ordering = ordering.to_owned();
+            return width <= self.lower_bound;
+        }
+        if self.reached.get(&before).is_some_and(|w| *w <= width) {
+            return false;
+        }
+        self.reached.insert(before, width);
+        let mut options: Vec<(usize, Vertex)> = before
+            .not()
+            .iter()
+            .map(|v| (self.cost(before, v).max(width), v))
+            .filter(|(w, _)| *w < self.best_width)
+            .collect();
+        options.sort();
+        for (new_width, v) in options {
+            ordering.push(v);
+            if self.branch(before.add_vert_immutable(v), ordering, new_width) {
+                return true;
+            }
+            ordering.pop();
+        }
+        false
+    }
+
+    fn run(mut self) -> (usize, Vec<Vertex>) {
+        if self.n.to_usize() <= MAX_DP_ORDER {
+            self.dp();
+        } else {
+            self.greedy();
+            if self.best_width > self.lower_bound {
+                self.branch(VertexSet::new(self.n), &mut vec![], 0);
+            }
+        }
+        (self.best_width, self.best_ordering)
+    }
+}
+
+pub fn treewidth(g: &Graph) -> u32 {
+    OrderingSearch::new(g, Width::Tree).run().0 as u32
+}
+
+pub fn pathwidth(g: &Graph) -> u32 {
+    OrderingSearch::new(g, Width::Path).run().0 as u32
+}
+
+pub fn tree_decomposition(g: &Graph) -> TreeDecomposition {
+    let (_, ordering) = OrderingSearch::new(g, Width::Tree).run();
+    TreeDecomposition::of_elimination_ordering(g, &ordering)
+}
+
+pub fn path_decomposition(g: &Graph) -> TreeDecomposition {
+    let (_, ordering) = OrderingSearch::new(g, Width::Path).run();
+    TreeDecomposition::path_of_ordering(g, &ordering)
+}
+
+fn print_checked(g: &Graph, decomposition: TreeDecomposition) {
+    if !decomposition.is_valid_for(g) {
+        panic!("Built an invalid decomposition!");
+    }
+    decomposition.print();
+}
+
+pub fn print_tree_decomposition(g: &Graph) {
+    print_checked(g, tree_decomposition(g));
+}
+
+pub fn print_path_decomposition(g: &Graph) {
+    print_checked(g, path_decomposition(g));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+
+    #[test]
+    fn test_treewidth() {
+        for (text, tw, pw) in [
+            ("p(7)", 1, 1),
+            ("k(1,5)", 1, 1),
+            ("c(8)", 2, 2),
+            ("k(6)", 5, 5),
+            ("k(3,4)", 3, 3),
+            ("grid(3,5)", 3, 3),
+            ("petersen", 4, 5),
+            ("e(4)", 0, 0),
+        ] {
+            let g = graph(text);
+            assert_eq!(treewidth(&g), tw, "{}", text);
+            assert_eq!(pathwidth(&g), pw, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_decompositions() {
+        for text in ["petersen", "grid(4,4)", "union(c(5),k(4))", "q(4)"] {
+            let g = graph(text);
+            let tree = tree_decomposition(&g);
+            assert!(tree.is_valid_for(&g));
+            assert_eq!(tree.width() as u32, treewidth(&g));
+            let path = path_decomposition(&g);
+            assert!(path.is_valid_for(&g));
+            assert_eq!(path.width() as u32, pathwidth(&g));
+        }
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        for (text, tw) in [("c(30)", 2), ("grid(3,8)", 3), ("k(4,20)", 4)] {
+            let g = graph(text);
+            assert_eq!(treewidth(&g), tw, "{}", text);
+            assert!(tree_decomposition(&g).is_valid_for(&g));
+        }
+        // The lower bound must not overshoot, or the search stops early.
+        let g = graph("grid(4,4)");
+        assert_eq!(OrderingSearch::new(&g, Width::Tree).degeneracy(), 2);
+        // Compare the branch and bound against the DP.
+        for _ in 0..5 {
+            let g = graph("erdos_renyi(12,0.3)");
+            for width in [Width::Tree, Width::Path] {
+                let mut search = OrderingSearch::new(&g, width);
+                search.greedy();
+                search.branch(VertexSet::new(g.n), &mut vec![], 0);
+                let mut dp = OrderingSearch::new(&g, width);
+                dp.dp();
+                assert_eq!(search.best_width, dp.best_width);
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use crate::entity::canonical::*;
+use crate::entity::graph::*;
+
+use utilities::polynomial::bivariate::*;
+use utilities::polynomial::*;
+
+// The Tutte polynomial by deletion-contraction. Contracting edges gives
+// multigraphs, which we store as matrices of edge multiplicities. Loops
+// would each just contribute a factor of y, so we never store them, and
+// instead contract a whole class of parallel edges at once.
+// The polynomial is multiplicative over connected components, and we
+// memoise the components by their canonical forms, so that isomorphic
+// minors are only ever computed once.
+
+type Multigraph = Vec<Vec<u32>>;
+
+type Memo = HashMap<Multigraph, BivariatePolynomial>;
+
+fn components(h: &Multigraph) -> Vec<Vec<usize>> {
+    let n = h.len();
+    let mut seen = vec![false; n];
+    let mut components = vec![];
+    for start in 0..n {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut component = vec![start];
+        let mut next = 0;
+        while next < component.len() {
+            let u = component[next];
+            for v in 0..n {
+                if h[u][v] > 0 && !seen[v] {
+                    seen[v] = true;
+                    component.push(v);
+                }
+            }
+            next += 1;
+        }
+        components.push(component);
+    }
+    components
+}
+
+fn induced(h: &Multigraph, verts: &[usize]) -> Multigraph {
+    verts
+        .iter()
+        .map(|u| verts.iter().map(|v| h[*u][*v]).collect())
+        .collect()
+}
+
+/**
+ * 1 + y + ... + y^(k - 1), with the 1 replaced by x if is_bridge.
+ */
+fn parallel_factor(k: u32, is_bridge: bool) -> BivariatePolynomial {
+    let mut factor = if is_bridge {
+        BivariatePolynomial::monomial(1, 1, 0)
+    } else {
+        BivariatePolynomial::monomial(1, 0, 0)
+    };
+    for j in 1..k as usize {
+        factor.add_inplace(&BivariatePolynomial::monomial(1, 0, j));
+    }
+    factor
+}
+
+/**
+ * Merges v into u, forgetting the edges between them.
+ */
+fn contract(h: &Multigraph, u: usize, v: usize) -> Multigraph {
+    let mut merged = h.to_owned();
+    for w in 0..h.len() {
+        if w != u && w != v {
+            merged[u][w] += h[v][w];
+            merged[w][u] += h[w][v];
+        }
+    }
+    merged[u][v] = 0;
+    merged[v][u] = 0;
+    merged.remove(v);
+    for row in merged.iter_mut() {
+        row.remove(v);
+    }
+    merged
+}
+
+/**
+ * The Tutte polynomial of a connected loopless multigraph.
+ */
+fn tutte_connected(h: &Multigraph, memo: &mut Memo) -> BivariatePolynomial {
+    let n = h.len();
+    if n <= 1 {
+        return BivariatePolynomial::monomial(1, 0, 0);
+    }
+    let lab = canonical_lab(h, &vec![0; n]);
+    let canonical: Multigraph = induced(h, &lab);
+    if let Some(poly) = memo.get(&canonical) {
+        return poly.to_owned();
+    }
+
+    // Take all the edges between a vertex v of smallest degree and one of
+    // its neighbours u. Deleting the first k - 1 of them and contracting
+    // the last gives T(G) = T(G - uv) + (1 + y + ... + y^(k - 1)) T(G / uv),
+    // and if the uv edges are a bridge then the 1 becomes an x and the
+    // deletion term vanishes.
+    let degree = |v: usize| h[v].iter().sum::<u32>();
+    let v = (0..n).min_by_key(|v| degree(*v)).unwrap();
+    let u = (0..n).find(|u| h[v][*u] > 0).unwrap();
+    let k = h[v][u];
+    let mut deleted = h.to_owned();
+    deleted[u][v] = 0;
+    deleted[v][u] = 0;
+    let is_bridge = components(&deleted).len() > 1;
+
+    let mut poly = parallel_factor(k, is_bridge).mul(&tutte(&contract(h, u, v), memo));
+    if !is_bridge {
+        poly.add_inplace(&tutte_connected(&deleted, memo));
+    }
+    memo.insert(canonical, poly.to_owned());
+    poly
+}
+
+fn tutte(h: &Multigraph, memo: &mut Memo) -> BivariatePolynomial {
+    let mut poly = BivariatePolynomial::monomial(1, 0, 0);
+    for component in components(h) {
+        if component.len() > 1 {
+            poly = poly.mul(&tutte_connected(&induced(h, &component), memo));
+        }
+    }
+    poly
+}
+
+pub fn tutte_polynomial(g: &Graph) -> BivariatePolynomial {
+    let h: Multigraph = g
+        .adj
+        .iter()
+        .map(|row| row.iter().map(|is_adj| *is_adj as u32).collect())
+        .collect();
+    tutte(&h, &mut HashMap::new())
+}
+
+/**
+ * The probability that the graph stays connected when each edge is kept
+ * independently with probability p. For connected G with n vertices and m
+ * edges, this is p^(n - 1) (1 - p)^(m - n + 1) T(1, 1 / (1 - p)), which
+ * expands to p^(n - 1) sum_j t_j (1 - p)^(m - n + 1 - j), where t_j is the
+ * coefficient of y^j in T(1, y).
+ */
+pub fn reliability_polynomial(g: &Graph, tutte: &BivariatePolynomial) -> Polynomial {
+    if !g.is_connected() {
+        return Polynomial::new();
+    }
+    let n = g.n.to_usize();
+    let nullity = g.size() + 1 - n;
+    let p = Polynomial::of_vec(&vec![0, 1]);
+    let one_minus_p = Polynomial::of_vec(&vec![1, -1]);
+    let mut sum = Polynomial::new();
+    for (j, t) in tutte.specialise_x(1).coefs().iter().enumerate() {
+        if *t == 0 {
+            continue;
+        }
+        let term = one_minus_p
+            .pow(nullity - j)
+            .mul(&Polynomial::of_vec(&vec![*t]));
+        sum.add_inplace(&term);
+    }
+    p.pow(n - 1).mul(&sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constructor::graph;
+    use utilities::edge_tools::*;
+    use utilities::vertex_tools::*;
+
+    #[test]
+    fn test_tutte_polynomial() {
+        // Trees give x^(n - 1), and cycles give x + ... + x^(n - 1) + y.
+        let tree = tutte_polynomial(&graph("p(5)"));
+        assert_eq!(tree, BivariatePolynomial::monomial(1, 4, 0));
+        let mut cycle = BivariatePolynomial::monomial(1, 0, 1);
+        for i in 1..5 {
+            cycle.add_inplace(&BivariatePolynomial::monomial(1, i, 0));
+        }
+        assert_eq!(tutte_polynomial(&graph("c(5)")), cycle);
+        // Padding with zero coefficients does not change the polynomial.
+        cycle.add_inplace(&BivariatePolynomial::monomial(0, 7, 3));
+        assert_eq!(tutte_polynomial(&graph("c(5)")), cycle);
+        // T(K_4) = x^3 + 3x^2 + 2x + 4xy + 2y + 3y^2 + y^3.
+        let k4 = tutte_polynomial(&graph("k(4)"));
+        for (i, j, c) in [
+            (3, 0, 1),
+            (2, 0, 3),
+            (1, 0, 2),
+            (1, 1, 4),
+            (0, 1, 2),
+            (0, 2, 3),
+            (0, 3, 1),
+        ] {
+            assert_eq!(k4.coef(i, j), c);
+        }
+        assert_eq!(k4.evaluate(1, 1), 16);
+    }
+
+    #[test]
+    fn test_tutte_evaluations() {
+        let petersen = tutte_polynomial(&graph("petersen"));
+        assert_eq!(petersen.evaluate(1, 1), 2000);
+        let cube = tutte_polynomial(&graph("q(3)"));
+        assert_eq!(cube.evaluate(1, 1), 384);
+        // Acyclic orientations of K_n are the n! orderings.
+        assert_eq!(tutte_polynomial(&graph("k(5)")).evaluate(2, 0), 120);
+        // Every edge set of a forest is a forest.
+        assert_eq!(tutte_polynomial(&graph("p(6)")).evaluate(2, 1), 32);
+        // Disconnected graphs multiply.
+        let two_triangles = tutte_polynomial(&graph("union(k(3),k(3))"));
+        assert_eq!(two_triangles.evaluate(1, 1), 9);
+        // K_{3,17} has 3^16 17^2 spanning trees, which is too many for a u32.
+        let k3_17 = tutte_polynomial(&graph("k(3,17)"));
+        assert_eq!(k3_17.evaluate(1, 1), 12440502369);
+    }
+
+    #[test]
+    fn test_reliability_polynomial() {
+        let c4 = graph("c(4)");
+        let reliability = reliability_polynomial(&c4, &tutte_polynomial(&c4));
+        // p^4 + 4 p^3 (1 - p) = 4 p^3 - 3 p^4.
+        assert_eq!(reliability.coefs(), &[0, 0, 0, 4, -3]);
+        let k4 = graph("k(4)");
+        let reliability = reliability_polynomial(&k4, &tutte_polynomial(&k4));
+        assert_eq!(reliability.evaluate(1.0), 1.0);
+        assert_eq!(reliability.evaluate(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_reliability_matches_percolation() {
+        // Count the edge sets connecting everything, as in percolate.rs.
+        for text in ["petersen", "k(2,3)", "union(c(3),k(1))"] {
+            let g = graph(text);
+            let m = g.size();
+            let indexer = EdgeIndexer::new(&g.adj_list);
+            let mut counts = vec![0; m + 1];
+            for edges in g.iter_edge_sets() {
+                let components = g.edge_subset_components(edges, &indexer);
+                if components.iter().all(|c| *c == components[Vertex::ZERO]) {
+                    counts[edges.size()] += 1;
+                }
+            }
+            let reliability = reliability_polynomial(&g, &tutte_polynomial(&g));
+            for p in [0.25_f64, 0.5, 0.9] {
+                let expected: f64 = counts
+                    .iter()
+                    .enumerate()
+                    .map(|(k, c)| (*c as f64) * p.powi(k as i32) * (1.0 - p).powi((m - k) as i32))
+                    .sum();
+                assert!((reliability.evaluate(p) - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -2,9 +2,12 @@ use utilities::*;
 
 use digraph::Digraph;
 
+pub mod canonical;
 pub mod digraph;
 pub mod graph;
+pub mod graph6;
 pub mod poset;
+pub mod tree_decomposition;
 
 use graph::*;
 use poset::*;
@@ -0,0 +1,590 @@
+use std::cmp::Ordering;
+
+use utilities::component_tools::*;
+use utilities::edge_tools::*;
+use utilities::vertex_tools::*;
+use utilities::*;
+
+// Canonical labelling by individualisation and refinement, in the style of
+// nauty. This works on an n x n matrix of weights, which need not be
+// symmetric, together with an initial colouring of the vertices, so graphs,
+// digraphs and posets can all be put through the same machinery.
+//
+// The canonical labelling is the one whose leaf in the search tree is
+// maximal, comparing first by the trace of node invariants along the path
+// to the leaf, and then by the relabelled matrix. Subtrees are pruned
+// whenever their trace falls below the best one, and whenever they are
+// equivalent under an automorphism we have already found.
+
+type Cells = Vec<Vec<usize>>;
+type Signature = Vec<(usize, u32, u32)>;
+
+// The node invariants are hashed with FNV-1a over explicit little-endian
+// words, so that canonical forms stay the same across platforms and
+// compiler versions.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv_add(hash: &mut u64, x: u64) {
+    for byte in x.to_le_bytes() {
+        *hash ^= byte as u64;
+        *hash = hash.wrapping_mul(FNV_PRIME);
+    }
+}
+
+#[derive(Clone)]
+struct Leaf {
+    path: Vec<usize>,
+    trace: Vec<u64>,
+    certificate: Vec<u32>,
+    lab: Vec<usize>,
+}
+
+pub struct CanonicalSearch<'a> {
+    n: usize,
+    matrix: &'a [Vec<u32>],
+    first: Option<Leaf>,
+    best: Option<Leaf>,
+    generators: Vec<Vec<usize>>,
+}
+
+/**
+ * Splits the cells into pieces of vertices with the same colour, in order
+ * of colour.
+ */
+fn cells_of_colours(colours: &[usize]) -> Cells {
+    let mut order: Vec<usize> = (0..colours.len()).collect();
+    order.sort_by_key(|v| colours[*v]);
+    let mut cells: Cells = vec![];
+    for v in order {
+        match cells.last_mut() {
+            Some(cell) if colours[cell[0]] == colours[v] => cell.push(v),
+            _ => cells.push(vec![v]),
+        }
+    }
+    cells
+}
+
+fn orbit_representatives(n: usize, generators: &[&Vec<usize>]) -> Vec<usize> {
+    fn find(parent: &mut [usize], x: usize) -> usize {
+        let mut root = x;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        let mut x = x;
+        while parent[x] != root {
+            let next = parent[x];
+            parent[x] = root;
+            x = next;
+        }
+        root
+    }
+    let mut parent: Vec<usize> = (0..n).collect();
+    for gamma in generators.iter() {
+        for (v, image) in gamma.iter().enumerate() {
+            let (a, b) = (find(&mut parent, v), find(&mut parent, *image));
+            if a != b {
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+    (0..n).map(|v| find(&mut parent, v)).collect()
+}
+
+impl<'a> CanonicalSearch<'a> {
+    pub fn new(matrix: &'a [Vec<u32>]) -> Self {
+        Self {
+            n: matrix.len(),
+            matrix,
+            first: None,
+            best: None,
+            generators: vec![],
+        }
+    }
+
+    /**
+     * The signature of v is the sorted list of (cell, weight out, weight in)
+     * over all the vertices which v sees with a nonzero weight in either
+     * direction.
+     */
+    fn signature(&self, v: usize, cell_of: &[usize]) -> Signature {
+        let mut sig: Signature = (0..self.n)
+            .filter_map(|w| {
+                let (out, inn) = (self.matrix[v][w], self.matrix[w][v]);
+                if out != 0 || inn != 0 {
+                    Some((cell_of[w], out, inn))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        sig.sort();
+        sig
+    }
+
+    fn cell_of(&self, cells: &Cells) -> Vec<usize> {
+        let mut cell_of = vec![0; self.n];
+        for (i, cell) in cells.iter().enumerate() {
+            for v in cell.iter() {
+                cell_of[*v] = i;
+            }
+        }
+        cell_of
+    }
+
+    /**
+     * Refines the cells until they are equitable. Cells are split in place,
+     * with the pieces ordered by signature, so this commutes with
+     * relabelling the vertices.
+     */
+    fn refine(&self, cells: &mut Cells) {
+        loop {
+            let cell_of = self.cell_of(cells);
+            let mut new_cells: Cells = vec![];
+            let mut changed = false;
+            for cell in cells.iter() {
+                if cell.len() == 1 {
+                    new_cells.push(cell.to_owned());
+                    continue;
+                }
+                let mut sigs: Vec<(Signature, usize)> = cell
+                    .iter()
+                    .map(|v| (self.signature(*v, &cell_of), *v))
+                    .collect();
+                sigs.sort();
+                let start = new_cells.len();
+                for (i, (sig, v)) in sigs.iter().enumerate() {
+                    if i == 0 || *sig != sigs[i - 1].0 {
+                        new_cells.push(vec![*v]);
+                    } else {
+                        new_cells.last_mut().unwrap().push(*v);
+                    }
+                }
+                if new_cells.len() > start + 1 {
+                    changed = true;
+                }
+            }
+            *cells = new_cells;
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /**
+     * A hash of the quotient of an equitable partition, which is invariant
+     * under relabelling the vertices.
+     */
+    fn invariant(&self, cells: &Cells) -> u64 {
+        let cell_of = self.cell_of(cells);
+        let mut hash = FNV_OFFSET_BASIS;
+        for cell in cells.iter() {
+            fnv_add(&mut hash, cell.len() as u64);
+            let signature = self.signature(cell[0], &cell_of);
+            fnv_add(&mut hash, signature.len() as u64);
+            for (other, weight_out, weight_in) in signature {
+                fnv_add(&mut hash, other as u64);
+                fnv_add(&mut hash, weight_out as u64);
+                fnv_add(&mut hash, weight_in as u64);
+            }
+        }
+        hash
+    }
+
+    fn certificate(&self, lab: &[usize]) -> Vec<u32> {
+        let mut cert = Vec::with_capacity(self.n * self.n);
+        for u in lab.iter() {
+            for v in lab.iter() {
+                cert.push(self.matrix[*u][*v]);
+            }
+        }
+        cert
+    }
+
+    fn add_automorphism(&mut self, from: &[usize], to: &[usize]) {
+        let mut gamma = vec![0; self.n];
+        for (u, v) in from.iter().zip(to.iter()) {
+            gamma[*u] = *v;
+        }
+        if gamma.iter().enumerate().any(|(v, image)| v != *image) {
+            self.generators.push(gamma);
+        }
+    }
+
+    /**
+     * Orbit representatives of the group generated by those automorphisms
+     * found so far which fix every vertex on the path.
+     */
+    fn orbits_fixing(&self, path: &[usize]) -> Vec<usize> {
+        let fixing: Vec<&Vec<usize>> = self
+            .generators
+            .iter()
+            .filter(|gamma| path.iter().all(|v| gamma[*v] == *v))
+            .collect();
+        orbit_representatives(self.n, &fixing)
+    }
+
+    /**
+     * Splits w off from the front of the target cell, and then refines.
+     */
+    fn individualise(&self, cells: &Cells, target: usize, w: usize) -> Cells {
+        let mut child = cells.to_owned();
+        let rest: Vec<usize> = child[target].iter().filter(|v| **v != w).copied().collect();
+        child[target] = vec![w];
+        child.insert(target + 1, rest);
+        self.refine(&mut child);
+        child
+    }
+
+    fn common_prefix(path1: &[usize], path2: &[usize]) -> usize {
+        path1
+            .iter()
+            .zip(path2.iter())
+            .take_while(|(x, y)| x == y)
+            .count()
+    }
+
+    /**
+     * Processes a leaf, returning the depth to jump back to if we found
+     * an automorphism which makes the rest of the current subtree redundant.
+     */
+    fn process_leaf(&mut self, cells: &Cells, path: &[usize], trace: &[u64]) -> Option<usize> {
+        let lab: Vec<usize> = cells.iter().map(|cell| cell[0]).collect();
+        let certificate = self.certificate(&lab);
+        let leaf = Leaf {
+            path: path.to_owned(),
+            trace: trace.to_owned(),
+            certificate,
+            lab,
+        };
+
+        let first = match &self.first {
+            None => {
+                self.first = Some(leaf.to_owned());
+                self.best = Some(leaf);
+                return None;
+            }
+            Some(first) => first,
+        };
+        if leaf.trace == first.trace && leaf.certificate == first.certificate {
+            let (from, jump) = (first.lab.to_owned(), Self::common_prefix(&first.path, path));
+            self.add_automorphism(&from, &leaf.lab);
+            return Some(jump);
+        }
+
+        let best = self.best.as_ref().unwrap();
+        match (&leaf.trace, &leaf.certificate).cmp(&(&best.trace, &best.certificate)) {
+            Ordering::Greater => {
+                self.best = Some(leaf);
+                None
+            }
+            Ordering::Equal => {
+                let (from, jump) = (best.lab.to_owned(), Self::common_prefix(&best.path, path));
+                self.add_automorphism(&from, &leaf.lab);
+                Some(jump)
+            }
+            Ordering::Less => None,
+        }
+    }
+
+    fn search(&mut self, cells: Cells, path: &mut Vec<usize>, trace: &mut Vec<u64>) -> Option<usize> {
+        trace.push(self.invariant(&cells));
+        if let Some(best) = &self.best {
+            // Every leaf below here would lose to the best leaf.
+            let len = trace.len().min(best.trace.len());
+            if trace[..len] < best.trace[..len] {
+                trace.pop();
+                return None;
+            }
+        }
+
+        let target = cells.iter().position(|cell| cell.len() > 1);
+        let result = match target {
+            None => self.process_leaf(&cells, path, trace),
+            Some(target) => {
+                let mut result = None;
+                let mut explored: Vec<usize> = vec![];
+                for w in cells[target].iter().copied() {
+                    // Skip w if an automorphism fixing the path maps it to
+                    // something we have already looked at.
+                    let reps = self.orbits_fixing(path);
+                    if explored.iter().any(|u| reps[*u] == reps[w]) {
+                        continue;
+                    }
+                    explored.push(w);
+                    let child = self.individualise(&cells, target, w);
+
+                    path.push(w);
+                    let jump = self.search(child, path, trace);
+                    path.pop();
+                    if let Some(level) = jump {
+                        if level < path.len() {
+                            result = Some(level);
+                            break;
+                        }
+                    }
+                }
+                result
+            }
+        };
+        trace.pop();
+        result
+    }
+
+    /**
+     * Searches the subtree below cells for a leaf which is equivalent to the
+     * first leaf, returning its lab if there is one.
+     */
+    fn find_equivalent(&self, cells: Cells, path: &mut Vec<usize>, first: &Leaf) -> Option<Vec<usize>> {
+        if first.trace.get(path.len()) != Some(&self.invariant(&cells)) {
+            return None;
+        }
+        match cells.iter().position(|cell| cell.len() > 1) {
+            None => {
+                let lab: Vec<usize> = cells.iter().map(|cell| cell[0]).collect();
+                if self.certificate(&lab) == first.certificate {
+                    Some(lab)
+                } else {
+                    None
+                }
+            }
+            Some(target) => {
+                // If u fails, then so does anything in the same orbit as u.
+                let reps = self.orbits_fixing(path);
+                let mut failed: Vec<usize> = vec![];
+                for w in cells[target].iter().copied() {
+                    if failed.iter().any(|u| reps[*u] == reps[w]) {
+                        continue;
+                    }
+                    let child = self.individualise(&cells, target, w);
+                    path.push(w);
+                    let lab = self.find_equivalent(child, path, first);
+                    path.pop();
+                    if lab.is_some() {
+                        return lab;
+                    }
+                    failed.push(w);
+                }
+                None
+            }
+        }
+    }
+
+    /**
+     * Computes a generating set for the automorphism group, along with its
+     * order (saturating at u128::MAX).
+     *
+     * We go back up the first path in the search tree. At each node, the
+     * stabiliser of the path so far is the union of cosets of the stabiliser
+     * one level deeper, one for each vertex w of the target cell to which
+     * the next vertex on the path can be mapped. We find these by searching
+     * below w for a leaf equivalent to the first leaf, so the generators
+     * found at each level generate the whole of that stabiliser.
+     */
+    pub fn automorphism_group(&mut self, colours: &[usize]) -> (Vec<Vec<usize>>, u128) {
+        if self.n == 0 {
+            return (vec![], 1);
+        }
+        let mut cells = cells_of_colours(colours);
+        self.refine(&mut cells);
+
+        let mut nodes: Vec<(Cells, usize)> = vec![];
+        let mut path = vec![];
+        let mut trace = vec![];
+        loop {
+            trace.push(self.invariant(&cells));
+            match cells.iter().position(|cell| cell.len() > 1) {
+                None => break,
+                Some(target) => {
+                    let w = cells[target][0];
+                    let child = self.individualise(&cells, target, w);
+                    nodes.push((cells, target));
+                    path.push(w);
+                    cells = child;
+                }
+            }
+        }
+        let lab: Vec<usize> = cells.iter().map(|cell| cell[0]).collect();
+        let first = Leaf {
+            path: path.to_owned(),
+            trace,
+            certificate: self.certificate(&lab),
+            lab,
+        };
+
+        let mut order: u128 = 1;
+        for (level, (node, target)) in nodes.iter().enumerate().rev() {
+            let prefix = &path[..level];
+            let v = path[level];
+            let mut failed: Vec<usize> = vec![];
+            for w in node[*target].iter().copied() {
+                let reps = self.orbits_fixing(prefix);
+                if reps[w] == reps[v] || failed.iter().any(|u| reps[*u] == reps[w]) {
+                    continue;
+                }
+                let child = self.individualise(node, *target, w);
+                let mut child_path = prefix.to_owned();
+                child_path.push(w);
+                match self.find_equivalent(child, &mut child_path, &first) {
+                    Some(lab) => self.add_automorphism(&first.lab, &lab),
+                    None => failed.push(w),
+                }
+            }
+            let reps = self.orbits_fixing(prefix);
+            let orbit_size = node[*target].iter().filter(|w| reps[**w] == reps[v]).count();
+            order = order.saturating_mul(orbit_size as u128);
+        }
+        (self.generators.to_owned(), order)
+    }
+
+    /**
+     * Returns lab, where lab[i] is the vertex which is given label i.
+     */
+    pub fn canonical_lab(&mut self, colours: &[usize]) -> Vec<usize> {
+        if self.n == 0 {
+            return vec![];
+        }
+        let mut cells = cells_of_colours(colours);
+        self.refine(&mut cells);
+        let _ = self.search(cells, &mut vec![], &mut vec![]);
+        self.best.to_owned().unwrap().lab
+    }
+}
+
+/**
+ * The automorphism group of a (possibly weighted, directed or coloured)
+ * matrix, as found by CanonicalSearch::automorphism_group.
+ */
+#[derive(Clone, Debug)]
+pub struct AutomorphismGroup {
+    n: Order,
+    generators: Vec<VertexVec<Vertex>>,
+    order: u128,
+}
+
+impl AutomorphismGroup {
+    pub fn of_matrix(matrix: &[Vec<u32>], colours: &[usize]) -> Self {
+        let (generators, order) = CanonicalSearch::new(matrix).automorphism_group(colours);
+        Self {
+            n: Order::of_usize(matrix.len()),
+            generators: generators
+                .iter()
+                .map(|gamma| gamma.iter().map(|v| Vertex::of_usize(*v)).collect())
+                .collect(),
+            order,
+        }
+    }
+
+    pub fn generators(&self) -> &Vec<VertexVec<Vertex>> {
+        &self.generators
+    }
+
+    /**
+     * The order of the group, or u128::MAX if it doesn't fit.
+     */
+    pub fn order(&self) -> u128 {
+        self.order
+    }
+
+    pub fn is_trivial(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    pub fn vertex_orbits(&self) -> UnionFind {
+        let mut orbits = UnionFind::new(self.n);
+        for gamma in self.generators.iter() {
+            for (v, image) in gamma.iter_enum() {
+                orbits.merge(v, *image);
+            }
+        }
+        orbits
+    }
+
+    /**
+     * The orbits of the edges indexed by the indexer, each in the order in
+     * which the indexer lists them.
+     */
+    pub fn edge_orbits(&self, indexer: &EdgeIndexer) -> Vec<Vec<Edge>> {
+        let num_edges = indexer.len();
+        let mut parent: Vec<usize> = (0..num_edges).collect();
+        for gamma in self.generators.iter() {
+            for (i, e) in indexer.iter_edges().enumerate() {
+                let image = Edge::of_pair(gamma[e.fst()], gamma[e.snd()]);
+                let (a, b) = (
+                    Self::find(&mut parent, i),
+                    Self::find(&mut parent, indexer.index_unwrap(image)),
+                );
+                if a != b {
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        let mut orbits: Vec<Vec<Edge>> = vec![];
+        let mut orbit_of_root: Vec<Option<usize>> = vec![None; num_edges];
+        for (i, e) in indexer.iter_edges().enumerate() {
+            let root = Self::find(&mut parent, i);
+            match orbit_of_root[root] {
+                Some(orbit) => orbits[orbit].push(*e),
+                None => {
+                    orbit_of_root[root] = Some(orbits.len());
+                    orbits.push(vec![*e]);
+                }
+            }
+        }
+        orbits
+    }
+
+    fn find(parent: &mut [usize], x: usize) -> usize {
+        let mut root = x;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[x] = root;
+        root
+    }
+}
+
+/**
+ * Writes an automorphism in cycle notation, leaving out fixed points.
+ */
+pub fn cycle_notation(gamma: &VertexVec<Vertex>) -> String {
+    let mut seen = VertexVec::new(gamma.len(), &false);
+    let mut out = String::new();
+    for (v, _) in gamma.iter_enum() {
+        if !seen[v] && gamma[v] != v {
+            let mut cycle = vec![];
+            let mut u = v;
+            while !seen[u] {
+                seen[u] = true;
+                cycle.push(format!("{}", u));
+                u = gamma[u];
+            }
+            out.push_str(&format!("({})", cycle.join(" ")));
+        }
+    }
+    if out.is_empty() {
+        out.push_str("()");
+    }
+    out
+}
+
+/**
+ * Returns lab, where lab[i] is the vertex given canonical label i.
+ */
+pub fn canonical_lab(matrix: &[Vec<u32>], colours: &[usize]) -> Vec<usize> {
+    CanonicalSearch::new(matrix).canonical_lab(colours)
+}
+
+pub fn matrix_of_adj(adj: &VertexVec<VertexVec<bool>>) -> Vec<Vec<u32>> {
+    adj.iter()
+        .map(|row| row.iter().map(|x| *x as u32).collect())
+        .collect()
+}
+
+/**
+ * Converts lab into an ordering of the vertices, as used by the various
+ * permute_vertices functions.
+ */
+pub fn ordering_of_lab(lab: &[usize]) -> VertexVec<Vertex> {
+    lab.iter().map(|v| Vertex::of_usize(*v)).collect()
+}
@@ -1,6 +1,7 @@
 use super::Digraph;
 
 use utilities::vertex_tools::*;
+use utilities::*;
 
 impl Digraph {
     pub fn has_source(&self) -> bool {
@@ -11,6 +12,10 @@ impl Digraph {
         self.iter_verts().any(|v| self.out_deg[v].equals(0))
     }
 
+    pub fn min_out_degree(&self) -> Degree {
+        *self.out_deg.iter().min().unwrap()
+    }
+
     /**
      * Returns v, where v[x] is the set of all those vertices y which are
      * connected by an edge x -> y.
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use crate::constructor::*;
+use crate::entity::canonical::AutomorphismGroup;
 use utilities::{component_tools::*, edge_tools::*, vertex_tools::*, *};
 use Constructor::*;
 use RawConstructor::*;
@@ -19,6 +20,8 @@ pub struct Graph {
     pub adj_list: VertexVec<Vec<Vertex>>,
     pub deg: VertexVec<Degree>,
     pub constructor: Constructor,
+    // Coordinates in the unit square, for graphs which come with a drawing.
+    pub positions: Option<VertexVec<(f64, f64)>>,
 }
 
 pub struct EdgeIterator<'a> {
@@ -47,6 +50,7 @@ impl Graph {
             adj_list,
             deg,
             constructor,
+            positions: None,
         }
     }
 
@@ -70,6 +74,7 @@ impl Graph {
             adj_list,
             deg,
             constructor,
+            positions: None,
         }
     }
 
@@ -104,6 +109,7 @@ impl Graph {
             adj_list,
             deg,
             constructor,
+            positions: None,
         }
     }
 
@@ -245,7 +251,12 @@ impl Graph {
             }
         }
 
-        (Self::of_adj_list(adj_list, Special), map)
+        let mut g = Self::of_adj_list(adj_list, Special);
+        g.positions = self
+            .positions
+            .as_ref()
+            .map(|positions| map.iter().map(|v| positions[*v]).collect());
+        (g, map)
     }
 
     /**
@@ -256,6 +267,14 @@ impl Graph {
         g
     }
 
+    /**
+     * Gives the graph a fixed drawing, which pretty-printing will use.
+     */
+    pub fn with_positions(mut self, positions: VertexVec<(f64, f64)>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
     pub fn bunkbed(&self) -> Self {
         let new_n = self.n + self.n;
         let mut adj_list = VertexVec::new_fn(new_n, |_| vec![]);
@@ -291,6 +310,41 @@ impl Graph {
         isomorphisms::is_isomorphic_to(self, g)
     }
 
+    /**
+     * Returns label, where label[v] is the canonical label of v. Two graphs
+     * are isomorphic if and only if relabelling each by its canonical
+     * labelling gives the same graph.
+     */
+    pub fn canonical_labelling(&self) -> VertexVec<Vertex> {
+        self.canonical_graph().1
+    }
+
+    /**
+     * Returns the graph relabelled by its canonical labelling, along with
+     * the labelling.
+     */
+    pub fn canonical_graph(&self) -> (Self, VertexVec<Vertex>) {
+        self.permute_vertices(&isomorphisms::canonical_ordering(self))
+    }
+
+    /**
+     * The graph6 code of the canonically labelled graph.
+     */
+    pub fn canonical_form(&self) -> String {
+        self.canonical_graph().0.to_graph6()
+    }
+
+    pub fn automorphism_group(&self) -> AutomorphismGroup {
+        isomorphisms::automorphism_group(self, VertexSet::new(self.n))
+    }
+
+    /**
+     * The subgroup of automorphisms which fix every vertex of fixed.
+     */
+    pub fn pointwise_stabiliser(&self, fixed: VertexSet) -> AutomorphismGroup {
+        isomorphisms::automorphism_group(self, fixed)
+    }
+
     /**
      * Only consider vertices for which filter[v] == true
      */
@@ -1,3 +1,4 @@
+use crate::entity::canonical;
 use crate::entity::graph::*;
 
 fn codeg_code(u: Vertex, v: Vertex) -> usize {
@@ -128,8 +129,8 @@ pub fn is_isomorphic_to(h: &Graph, g: &Graph) -> bool {
     }
 
     if n > 15 {
-        // give up; would be too slow
-        return false;
+        // Backtracking would be too slow, so compare canonical forms instead.
+        return h.canonical_form() == g.canonical_form();
     }
 
     // BFS on self to find ordering.
@@ -174,3 +175,114 @@ pub fn is_isomorphic_to(h: &Graph, g: &Graph) -> bool {
     }
     is_iso
 }
+
+/**
+ * Returns the canonical ordering of the vertices of g, i.e. ordering[i] is
+ * the vertex which gets canonical label i.
+ */
+pub fn canonical_ordering(g: &Graph) -> VertexVec<Vertex> {
+    let matrix = canonical::matrix_of_adj(&g.adj);
+    let lab = canonical::canonical_lab(&matrix, &vec![0; g.n.to_usize()]);
+    canonical::ordering_of_lab(&lab)
+}
+
+pub fn automorphism_group(g: &Graph, fixed: VertexSet) -> canonical::AutomorphismGroup {
+    let matrix = canonical::matrix_of_adj(&g.adj);
+    // Give each fixed vertex a colour of its own.
+    let colours: Vec<usize> = g
+        .iter_verts()
+        .enumerate()
+        .map(|(i, v)| if fixed.has_vert(v) { i + 1 } else { 0 })
+        .collect();
+    canonical::AutomorphismGroup::of_matrix(&matrix, &colours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_canonical_form_invariant_under_permutation() {
+        for n in 1..25 {
+            let g = Constructor::of_string(&format!("er({},0.3)", n))
+                .new_entity()
+                .as_owned_graph();
+            let (h, _) = g.randomly_permute_vertices();
+            assert_eq!(g.canonical_form(), h.canonical_form());
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_symmetric_graphs() {
+        for constr in ["petersen", "dodecahedron", "q(5)", "k(7,7)", "e(12)", "grid(4,5)"] {
+            let g = Constructor::of_string(constr).new_entity().as_owned_graph();
+            let (h, _) = g.randomly_permute_vertices();
+            assert_eq!(g.canonical_form(), h.canonical_form());
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_is_stable() {
+        // Canonical forms are meant for looking graphs up, so they must not
+        // change between runs or builds.
+        for (constr, form) in [
+            ("petersen", "IsP@PGXD_"),
+            ("grid(3,4)", "K?os@D?CGT?j"),
+            ("k(2,3)", "DFw"),
+        ] {
+            let g = Constructor::of_string(constr).new_entity().as_owned_graph();
+            assert_eq!(g.canonical_form(), form, "{}", constr);
+        }
+    }
+
+    #[test]
+    fn test_automorphism_group_orders() {
+        for (constr, order) in [
+            ("petersen", 120),
+            ("dodecahedron", 120),
+            ("q(4)", 384),
+            ("k(3,4)", 144),
+            ("c(9)", 18),
+            ("e(10)", 3628800),
+            ("k(7)", 5040),
+            ("grid(3,4)", 4),
+        ] {
+            let g = Constructor::of_string(constr).new_entity().as_owned_graph();
+            let (h, _) = g.randomly_permute_vertices();
+            assert_eq!(h.automorphism_group().order(), order, "{}", constr);
+        }
+    }
+
+    #[test]
+    fn test_automorphism_group_orbits() {
+        let g = Constructor::of_string("star(6)").new_entity().as_owned_graph();
+        let group = g.automorphism_group();
+        assert_eq!(group.vertex_orbits().get_representatives().size(), 2);
+        let indexer = EdgeIndexer::new(&g.adj_list);
+        assert_eq!(group.edge_orbits(&indexer).len(), 1);
+
+        let leaf = g.iter_verts().find(|v| g.deg[*v].equals(1)).unwrap();
+        let stabiliser = g.pointwise_stabiliser(VertexSet::of_vert(g.n, leaf));
+        assert_eq!(stabiliser.order(), 24);
+        assert_eq!(stabiliser.vertex_orbits().get_representatives().size(), 3);
+    }
+
+    #[test]
+    fn test_canonical_form_counts_graphs_5() {
+        // There are 34 graphs on five vertices up to isomorphism.
+        let order = Order::of_usize(5);
+        let mut forms = HashSet::new();
+        for code in 0..(1_u32 << 10) {
+            let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+            for (i, (u, v)) in order.iter_pairs().enumerate() {
+                if (code >> i) & 1 == 1 {
+                    adj[u][v] = true;
+                    adj[v][u] = true;
+                }
+            }
+            forms.insert(Graph::of_matrix(adj, Special).canonical_form());
+        }
+        assert_eq!(forms.len(), 34);
+    }
+}
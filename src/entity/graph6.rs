@@ -0,0 +1,305 @@
+use crate::constructor::*;
+use crate::entity::digraph::Digraph;
+use crate::entity::graph::Graph;
+use crate::entity::Entity;
+
+use utilities::vertex_tools::*;
+use utilities::*;
+
+// Readers and writers for the graph6, sparse6 and digraph6 formats used by
+// nauty, House of Graphs and SageMath. Full specification:
+// https://users.cecs.anu.edu.au/~bdm/data/formats.txt
+
+const BIAS: u8 = 63;
+const SPARSE6_PREFIX: char = ':';
+const DIGRAPH6_PREFIX: char = '&';
+
+fn encode_order(n: usize, out: &mut String) {
+    if n <= 62 {
+        out.push((n as u8 + BIAS) as char);
+    } else if n <= 258047 {
+        out.push('~');
+        for shift in [12, 6, 0] {
+            out.push((((n >> shift) & 63) as u8 + BIAS) as char);
+        }
+    } else {
+        out.push_str("~~");
+        for shift in [30, 24, 18, 12, 6, 0] {
+            out.push((((n >> shift) & 63) as u8 + BIAS) as char);
+        }
+    }
+}
+
+/**
+ * Returns the order encoded at the start of the bytes, along with the
+ * number of bytes used to encode it.
+ */
+fn decode_order(bytes: &[u8]) -> (usize, usize) {
+    fn read(bytes: &[u8]) -> usize {
+        bytes
+            .iter()
+            .fold(0, |acc, b| (acc << 6) | (b - BIAS) as usize)
+    }
+    if bytes[0] != b'~' {
+        (read(&bytes[0..1]), 1)
+    } else if bytes[1] != b'~' {
+        (read(&bytes[1..4]), 4)
+    } else {
+        (read(&bytes[2..8]), 8)
+    }
+}
+
+/**
+ * Packs the bits into six-bit chunks, padding the final chunk with zeroes.
+ */
+fn pack_bits(bits: &[bool], out: &mut String) {
+    for chunk in bits.chunks(6) {
+        let mut byte = 0;
+        for i in 0..6 {
+            byte <<= 1;
+            if chunk.get(i) == Some(&true) {
+                byte |= 1;
+            }
+        }
+        out.push((byte + BIAS) as char);
+    }
+}
+
+fn unpack_bits(bytes: &[u8]) -> Vec<bool> {
+    let mut bits = vec![];
+    for b in bytes.iter() {
+        let b = b - BIAS;
+        for i in (0..6).rev() {
+            bits.push((b >> i) & 1 == 1);
+        }
+    }
+    bits
+}
+
+fn strip_header<'a>(code: &'a str, header: &str) -> &'a str {
+    code.trim().strip_prefix(header).unwrap_or(code.trim())
+}
+
+/**
+ * Decodes a single line in any of the three formats, deciding which one
+ * to use based on the leading character.
+ */
+pub fn new_entity(code: &str) -> Entity {
+    let code = code.trim();
+    if code.starts_with(">>sparse6<<") || code.starts_with(SPARSE6_PREFIX) {
+        Entity::Graph(Graph::of_sparse6(code))
+    } else if code.starts_with(">>digraph6<<") || code.starts_with(DIGRAPH6_PREFIX) {
+        Entity::Digraph(Digraph::of_digraph6(code))
+    } else {
+        Entity::Graph(Graph::of_graph6(code))
+    }
+}
+
+impl Graph {
+    pub fn to_graph6(&self) -> String {
+        let mut out = String::new();
+        encode_order(self.n.to_usize(), &mut out);
+        let mut bits = vec![];
+        for j in self.iter_verts() {
+            for i in self.iter_verts().take_while(|i| *i < j) {
+                bits.push(self.adj[i][j]);
+            }
+        }
+        pack_bits(&bits, &mut out);
+        out
+    }
+
+    pub fn of_graph6(code: &str) -> Self {
+        let bytes = strip_header(code, ">>graph6<<").as_bytes();
+        let (n, offset) = decode_order(bytes);
+        let order = Order::of_usize(n);
+        let bits = unpack_bits(&bytes[offset..]);
+        if bits.len() < (n * n.saturating_sub(1)) / 2 {
+            panic!("graph6 code {} is too short for order {}!", code, n);
+        }
+        let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+        let mut index = 0;
+        for j in order.iter_verts() {
+            for i in order.iter_verts().take_while(|i| *i < j) {
+                if bits[index] {
+                    adj[i][j] = true;
+                    adj[j][i] = true;
+                }
+                index += 1;
+            }
+        }
+        Self::of_matrix(adj, Constructor::Encoded(code.trim().to_owned()))
+    }
+
+    pub fn to_sparse6(&self) -> String {
+        let n = self.n.to_usize();
+        let mut out = String::new();
+        out.push(SPARSE6_PREFIX);
+        encode_order(n, &mut out);
+
+        // Number of bits needed to write down n - 1.
+        let k = (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize;
+        let mut bits = vec![];
+        let push_num = |bits: &mut Vec<bool>, x: usize| {
+            for i in (0..k).rev() {
+                bits.push((x >> i) & 1 == 1);
+            }
+        };
+
+        let mut last_j = 0;
+        let mut last_j_has_edge = false;
+        for (j, v) in self.iter_verts().enumerate() {
+            for (i, u) in self.iter_verts().enumerate().take(j) {
+                if !self.adj[u][v] {
+                    continue;
+                }
+                if j == last_j {
+                    bits.push(false);
+                } else if j == last_j + 1 {
+                    bits.push(true);
+                } else {
+                    bits.push(true);
+                    push_num(&mut bits, j);
+                    bits.push(false);
+                }
+                push_num(&mut bits, i);
+                last_j = j;
+                last_j_has_edge = true;
+            }
+        }
+
+        let padding = (6 - bits.len() % 6) % 6;
+        if k < 6 && n == (1 << k) && last_j_has_edge && last_j == n - 2 && padding > k {
+            // Padding with ones would otherwise be read as a loop at n - 1.
+            bits.push(false);
+        }
+        while bits.len() % 6 != 0 {
+            bits.push(true);
+        }
+        pack_bits(&bits, &mut out);
+        out
+    }
+
+    pub fn of_sparse6(code: &str) -> Self {
+        let trimmed = strip_header(code, ">>sparse6<<");
+        let bytes = match trimmed.strip_prefix(SPARSE6_PREFIX) {
+            Some(rest) => rest.as_bytes(),
+            None => panic!("sparse6 code {} must start with ':'!", code),
+        };
+        let (n, offset) = decode_order(bytes);
+        let order = Order::of_usize(n);
+        let bits = unpack_bits(&bytes[offset..]);
+        let k = (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize;
+
+        let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+        let mut index = 0;
+        let mut v = 0;
+        while index + k < bits.len() {
+            if bits[index] {
+                v += 1;
+            }
+            let x = bits[index + 1..=index + k]
+                .iter()
+                .fold(0, |acc, b| (acc << 1) | *b as usize);
+            index += k + 1;
+            if x > v {
+                v = x;
+            } else if v < n && x != v {
+                // Loops are allowed by the format, but not by us.
+                let (a, b) = (Vertex::of_usize(x), Vertex::of_usize(v));
+                adj[a][b] = true;
+                adj[b][a] = true;
+            }
+        }
+        Self::of_matrix(adj, Constructor::Encoded(code.trim().to_owned()))
+    }
+}
+
+impl Digraph {
+    pub fn to_digraph6(&self) -> String {
+        let mut out = String::new();
+        out.push(DIGRAPH6_PREFIX);
+        encode_order(self.n.to_usize(), &mut out);
+        let mut bits = vec![];
+        for i in self.iter_verts() {
+            for j in self.iter_verts() {
+                bits.push(self.adj[i][j]);
+            }
+        }
+        pack_bits(&bits, &mut out);
+        out
+    }
+
+    pub fn of_digraph6(code: &str) -> Self {
+        let trimmed = strip_header(code, ">>digraph6<<");
+        let bytes = match trimmed.strip_prefix(DIGRAPH6_PREFIX) {
+            Some(rest) => rest.as_bytes(),
+            None => panic!("digraph6 code {} must start with '&'!", code),
+        };
+        let (n, offset) = decode_order(bytes);
+        let order = Order::of_usize(n);
+        let bits = unpack_bits(&bytes[offset..]);
+        if bits.len() < n * n {
+            panic!("digraph6 code {} is too short for order {}!", code, n);
+        }
+        let mut adj = VertexVec::new(order, &VertexVec::new(order, &false));
+        let mut index = 0;
+        for i in order.iter_verts() {
+            for j in order.iter_verts() {
+                adj[i][j] = bits[index];
+                index += 1;
+            }
+        }
+        Self::of_matrix(adj, vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adj_matrices_match(adj1: &VertexVec<VertexVec<bool>>, adj2: &VertexVec<VertexVec<bool>>) -> bool {
+        adj1.len() == adj2.len()
+            && adj1
+                .iter_enum()
+                .all(|(v, row)| row.iter_enum().all(|(u, x)| *x == adj2[v][u]))
+    }
+
+    #[test]
+    fn test_graph6_petersen() {
+        let g = Constructor::of_string("petersen").new_entity().as_owned_graph();
+        let h = Graph::of_graph6("IheA@GUAo");
+        assert_eq!(h.size(), 15);
+        assert!(g.is_isomorphic_to(&h));
+        assert!(adj_matrices_match(&h.adj, &Graph::of_graph6(&h.to_graph6()).adj));
+    }
+
+    #[test]
+    fn test_graph6_k4() {
+        assert_eq!(Graph::new_complete(Order::of_usize(4)).to_graph6(), "C~");
+    }
+
+    #[test]
+    fn test_sparse6_spec_example() {
+        let g = Graph::of_sparse6(":Fa@x^");
+        assert_eq!(g.n, Order::of_usize(7));
+        assert_eq!(g.size(), 4);
+        assert_eq!(g.to_sparse6(), ":Fa@x^");
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for n in 1..20 {
+            let g = Constructor::of_string(&format!("er({},0.4)", n))
+                .new_entity()
+                .as_owned_graph();
+            assert!(adj_matrices_match(&g.adj, &Graph::of_graph6(&g.to_graph6()).adj));
+            assert!(adj_matrices_match(&g.adj, &Graph::of_sparse6(&g.to_sparse6()).adj));
+
+            let d = Constructor::of_string(&format!("oriented({})", n))
+                .new_entity()
+                .as_owned_digraph();
+            assert!(adj_matrices_match(&d.adj, &Digraph::of_digraph6(&d.to_digraph6()).adj));
+        }
+    }
+}
@@ -21,7 +21,7 @@ pub struct Poset {
     pub upsets: VertexVec<VertexSet>,
     pub heights: VertexVec<usize>,
     pub height: usize,
-    constructor: Constructor,
+    pub constructor: Constructor,
 }
 
 /***
@@ -0,0 +1,171 @@
+use crate::entity::graph::*;
+
+use utilities::vertex_tools::*;
+
+// Tree decompositions, built from vertex orderings. Eliminating the
+// vertices in order, joining up the later neighbours of each one, gives a
+// tree decomposition whose bags are the vertices with those neighbours, and
+// every tree decomposition of minimal width arises like this. Reading the
+// ordering as a layout instead gives a path decomposition, whose bags are
+// each vertex together with the earlier vertices still waiting for a later
+// neighbour.
+
+#[derive(Clone, Debug)]
+pub struct TreeDecomposition {
+    pub bags: Vec<Vec<Vertex>>,
+    // The tree on the bags, as an adjacency list of bag indices.
+    pub adj_list: Vec<Vec<usize>>,
+}
+
+impl TreeDecomposition {
+    fn join(&mut self, i: usize, j: usize) {
+        self.adj_list[i].push(j);
+        self.adj_list[j].push(i);
+    }
+
+    pub fn of_elimination_ordering(g: &Graph, ordering: &[Vertex]) -> TreeDecomposition {
+        let mut position = VertexVec::new(g.n, &0);
+        for (i, v) in ordering.iter().enumerate() {
+            position[*v] = i;
+        }
+        let mut adj = g.adj.to_owned();
+        let mut decomposition = TreeDecomposition {
+            bags: vec![],
+            adj_list: vec![vec![]; ordering.len()],
+        };
+        let mut parents = vec![];
+        for (i, v) in ordering.iter().enumerate() {
+            let later_nbrs: Vec<Vertex> = g
+                .iter_verts()
+                .filter(|u| adj[*v][*u] && position[*u] > i)
+                .collect();
+            for x in later_nbrs.iter() {
+                for y in later_nbrs.iter() {
+                    if x != y {
+                        adj[*x][*y] = true;
+                    }
+                }
+            }
+            parents.push(later_nbrs.iter().map(|u| position[*u]).min());
+            let mut bag = vec![*v];
+            bag.extend(later_nbrs);
+            bag.sort();
+            decomposition.bags.push(bag);
+        }
+        // Bags without a parent are the roots of the components, which we
+        // chain together to get a single tree.
+        let mut last_root: Option<usize> = None;
+        for (i, parent) in parents.iter().enumerate() {
+            match parent {
+                Some(j) => decomposition.join(i, *j),
+                None => {
+                    if let Some(j) = last_root {
+                        decomposition.join(i, j);
+                    }
+                    last_root = Some(i);
+                }
+            }
+        }
+        decomposition
+    }
+
+    pub fn path_of_ordering(g: &Graph, ordering: &[Vertex]) -> TreeDecomposition {
+        let mut position = VertexVec::new(g.n, &0);
+        for (i, v) in ordering.iter().enumerate() {
+            position[*v] = i;
+        }
+        let mut decomposition = TreeDecomposition {
+            bags: vec![],
+            adj_list: vec![vec![]; ordering.len()],
+        };
+        for (i, v) in ordering.iter().enumerate() {
+            let mut bag: Vec<Vertex> = ordering[..i]
+                .iter()
+                .filter(|u| g.adj_list[**u].iter().any(|w| position[*w] >= i))
+                .copied()
+                .collect();
+            bag.push(*v);
+            bag.sort();
+            decomposition.bags.push(bag);
+            if i > 0 {
+                decomposition.join(i - 1, i);
+            }
+        }
+        decomposition
+    }
+
+    /**
+     * One less than the size of the largest bag.
+     */
+    pub fn width(&self) -> usize {
+        self.bags.iter().map(|bag| bag.len()).max().unwrap_or(1) - 1
+    }
+
+    /**
+     * Are the bags containing v connected in the tree?
+     */
+    fn is_subtree_connected(&self, v: Vertex) -> bool {
+        let contains: Vec<bool> = self.bags.iter().map(|bag| bag.contains(&v)).collect();
+        let Some(start) = contains.iter().position(|x| *x) else {
+            return false;
+        };
+        let mut seen = vec![false; self.bags.len()];
+        seen[start] = true;
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            for j in self.adj_list[i].iter() {
+                if contains[*j] && !seen[*j] {
+                    seen[*j] = true;
+                    stack.push(*j);
+                }
+            }
+        }
+        contains.iter().zip(seen.iter()).all(|(c, s)| !c || *s)
+    }
+
+    fn is_tree(&self) -> bool {
+        let num_edges: usize = self.adj_list.iter().map(|nbrs| nbrs.len()).sum::<usize>() / 2;
+        if self.bags.is_empty() {
+            return true;
+        } else if num_edges + 1 != self.bags.len() {
+            return false;
+        }
+        let mut seen = vec![false; self.bags.len()];
+        seen[0] = true;
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            for j in self.adj_list[i].iter() {
+                if !seen[*j] {
+                    seen[*j] = true;
+                    stack.push(*j);
+                }
+            }
+        }
+        seen.iter().all(|x| *x)
+    }
+
+    /**
+     * Checks that the bags sit on a tree, cover every edge, and that the
+     * bags containing each vertex form a subtree.
+     */
+    pub fn is_valid_for(&self, g: &Graph) -> bool {
+        self.is_tree()
+            && g.iter_verts().all(|v| self.is_subtree_connected(v))
+            && g.iter_pairs().all(|(u, v)| {
+                !g.adj[u][v]
+                    || self
+                        .bags
+                        .iter()
+                        .any(|bag| bag.contains(&u) && bag.contains(&v))
+            })
+    }
+
+    pub fn print(&self) {
+        println!("Width {} with {} bags", self.width(), self.bags.len());
+        for (i, bag) in self.bags.iter().enumerate() {
+            let verts: Vec<String> = bag.iter().map(|v| v.to_string()).collect();
+            let nbrs: Vec<String> = self.adj_list[i].iter().map(|j| j.to_string()).collect();
+            println!("{}: {{{}}} ~ {}", i, verts.join(", "), nbrs.join(", "));
+        }
+    }
+}
@@ -0,0 +1,977 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::constructor::Constructor;
+use crate::entity::canonical::*;
+use crate::entity::graph::Graph;
+use crate::operation::bool_operation::*;
+use crate::operation::int_operation::IntOperation;
+
+use utilities::vertex_tools::*;
+use utilities::*;
+
+pub mod digraphs;
+pub mod posets;
+pub mod trees;
+
+// Exhaustive generation of graphs up to isomorphism, by McKay's canonical
+// augmentation. Each graph on k + 1 vertices is built from a graph on k
+// vertices by adding a vertex, and is only accepted if the new vertex is
+// in the same orbit as the canonically chosen vertex to delete. Every
+// unlabelled graph is then produced exactly once.
+
+/**
+ * Restrictions on the graphs to generate. Those which are hereditary
+ * (maximum degree, girth, triangle-free, bipartite) prune the search, the
+ * others only cut it down near the end.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct GraphFilter {
+    pub min_degree: Option<usize>,
+    pub max_degree: Option<usize>,
+    pub connected: bool,
+    pub triangle_free: bool,
+    pub bipartite: bool,
+    pub min_girth: Option<usize>,
+}
+
+impl GraphFilter {
+    /**
+     * Picks out whichever of the conditions the generator can use. The
+     * conditions should still all be checked on the graphs produced.
+     */
+    pub fn of_conditions(conditions: &[BoolOperation]) -> Self {
+        use BoolOperation::*;
+        use IntOperation::*;
+        use NumToBoolInfix::*;
+        let mut filter = Self::default();
+        for condition in conditions.iter() {
+            match condition {
+                IsConnected => filter.connected = true,
+                IsTriangleFree => filter.triangle_free = true,
+                IsBipartite => {
+                    filter.bipartite = true;
+                    filter.triangle_free = true;
+                }
+                IsKConnected(k) if *k >= 1 => filter.connected = true,
+                IntInfix(infix, op, Number(x)) => {
+                    let x = *x as usize;
+                    let (lower, upper) = match infix {
+                        More => (Some(x + 1), None),
+                        NotLess => (Some(x), None),
+                        Less => (None, Some(x.saturating_sub(1))),
+                        NotMore => (None, Some(x)),
+                        Equal => (Some(x), Some(x)),
+                        NotEqual => (None, None),
+                    };
+                    match op {
+                        MinDegree => filter.tighten_min_degree(lower),
+                        MaxDegree => filter.tighten_max_degree(upper),
+                        Girth => filter.min_girth = lower.max(filter.min_girth),
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+        filter
+    }
+
+    fn tighten_min_degree(&mut self, bound: Option<usize>) {
+        if let Some(d) = bound {
+            self.min_degree = Some(self.min_degree.map_or(d, |old| old.max(d)));
+        }
+    }
+
+    fn tighten_max_degree(&mut self, bound: Option<usize>) {
+        if let Some(d) = bound {
+            self.max_degree = Some(self.max_degree.map_or(d, |old| old.min(d)));
+        }
+    }
+}
+
+impl fmt::Display for GraphFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(d) = self.min_degree {
+            parts.push(format!("min degree >= {}", d));
+        }
+        if let Some(d) = self.max_degree {
+            parts.push(format!("max degree <= {}", d));
+        }
+        if let Some(g) = self.min_girth {
+            parts.push(format!("girth >= {}", g));
+        }
+        if self.connected {
+            parts.push("connected".to_owned());
+        }
+        if self.bipartite {
+            parts.push("bipartite".to_owned());
+        } else if self.triangle_free {
+            parts.push("triangle-free".to_owned());
+        }
+        if parts.is_empty() {
+            write!(f, "no restrictions")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+fn bit(v: usize) -> u128 {
+    1 << v
+}
+
+fn is_connected(adj: &[u128]) -> bool {
+    if adj.is_empty() {
+        return true;
+    }
+    let everything = if adj.len() == 128 {
+        u128::MAX
+    } else {
+        bit(adj.len()) - 1
+    };
+    let mut reached = bit(0);
+    let mut frontier = bit(0);
+    while frontier != 0 {
+        let v = frontier.trailing_zeros() as usize;
+        frontier &= !bit(v);
+        let new = adj[v] & !reached;
+        reached |= new;
+        frontier |= new;
+    }
+    reached == everything
+}
+
+fn is_bipartite(adj: &[u128]) -> bool {
+    let mut colour: Vec<Option<bool>> = vec![None; adj.len()];
+    for start in 0..adj.len() {
+        if colour[start].is_some() {
+            continue;
+        }
+        colour[start] = Some(false);
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            let c = colour[v].unwrap();
+            let mut nbrs = adj[v];
+            while nbrs != 0 {
+                let u = nbrs.trailing_zeros() as usize;
+                nbrs &= !bit(u);
+                match colour[u] {
+                    None => {
+                        colour[u] = Some(!c);
+                        stack.push(u);
+                    }
+                    Some(d) if d == c => return false,
+                    Some(_) => (),
+                }
+            }
+        }
+    }
+    true
+}
+
+/**
+ * A bipartite graph with a fixed bipartition, in which every vertex on the
+ * left has degree left_degree and every vertex on the right has degree
+ * right_degree.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Biregular {
+    pub left_order: usize,
+    pub left_degree: usize,
+    pub right_order: usize,
+    pub right_degree: usize,
+}
+
+impl Biregular {
+    fn order(&self, side: usize) -> usize {
+        if side == 0 {
+            self.left_order
+        } else {
+            self.right_order
+        }
+    }
+
+    fn degree(&self, side: usize) -> usize {
+        if side == 0 {
+            self.left_degree
+        } else {
+            self.right_degree
+        }
+    }
+}
+
+impl fmt::Display for Biregular {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}, {})-biregular with sides of orders {} and {}",
+            self.left_degree, self.right_degree, self.left_order, self.right_order
+        )
+    }
+}
+
+/**
+ * The vertices which are at distance at most max_dist from v.
+ */
+fn ball(adj: &[u128], v: usize, max_dist: usize) -> u128 {
+    let everything = bit(adj.len()) - 1;
+    let mut reached = bit(v);
+    let mut frontier = bit(v);
+    for _ in 0..max_dist {
+        let mut next = 0;
+        while frontier != 0 {
+            let u = frontier.trailing_zeros() as usize;
+            frontier &= !bit(u);
+            next |= adj[u];
+        }
+        frontier = next & everything & !reached;
+        reached |= frontier;
+    }
+    reached
+}
+
+/**
+ * Iterates over the subsets of a set of vertices.
+ */
+fn subsets(set: u128) -> impl Iterator<Item = u128> {
+    let mut next: Option<u128> = Some(0);
+    std::iter::from_fn(move || {
+        let current = next?;
+        let following = current.wrapping_sub(set) & set;
+        next = if following == 0 {
+            None
+        } else {
+            Some(following)
+        };
+        Some(current)
+    })
+}
+
+/**
+ * Iterates over the subsets of a set of vertices with between min_size and
+ * max_size elements, in order of size.
+ */
+fn subsets_of_sizes(set: u128, min_size: usize, max_size: usize) -> impl Iterator<Item = u128> {
+    let elements: Vec<usize> = (0..128).filter(|v| set & bit(*v) != 0).collect();
+    let max_size = max_size.min(elements.len());
+    (min_size..=max_size).flat_map(move |size| {
+        let elements = elements.to_owned();
+        let m = elements.len();
+        let mut indices: Option<Vec<usize>> = Some((0..size).collect());
+        std::iter::from_fn(move || {
+            let current = indices.take()?;
+            let subset = current
+                .iter()
+                .fold(0, |subset, i| subset | bit(elements[*i]));
+            // Move the last index which can move, and put the ones after it
+            // straight after it.
+            let mut i = size;
+            while i > 0 && current[i - 1] == m - size + i - 1 {
+                i -= 1;
+            }
+            if i > 0 {
+                let mut next = current;
+                next[i - 1] += 1;
+                for j in i..size {
+                    next[j] = next[j - 1] + 1;
+                }
+                indices = Some(next);
+            }
+            Some(subset)
+        })
+    })
+}
+
+/**
+ * The graph built so far. colours[v] is the side of v when generating
+ * biregular graphs, and is 0 otherwise.
+ */
+#[derive(Clone)]
+struct Partial {
+    adj: Vec<u128>,
+    colours: Vec<usize>,
+}
+
+impl Partial {
+    fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn deg(&self, v: usize) -> usize {
+        self.adj[v].count_ones() as usize
+    }
+
+    fn num_of_colour(&self, colour: usize) -> usize {
+        self.colours.iter().filter(|c| **c == colour).count()
+    }
+
+    fn with_vertex(&self, colour: usize, nbrs: u128) -> Self {
+        let k = self.len();
+        let mut child = self.to_owned();
+        for (v, row) in child.adj.iter_mut().enumerate() {
+            if nbrs & bit(v) != 0 {
+                *row |= bit(k);
+            }
+        }
+        child.adj.push(nbrs);
+        child.colours.push(colour);
+        child
+    }
+}
+
+struct Generator<'a> {
+    target: usize,
+    filter: &'a GraphFilter,
+    sides: Option<Biregular>,
+}
+
+impl<'a> Generator<'a> {
+    fn min_degree(&self, colour: usize) -> usize {
+        let min = self.filter.min_degree.unwrap_or(0);
+        self.sides
+            .map_or(min, |sides| min.max(sides.degree(colour)))
+    }
+
+    fn max_degree(&self, colour: usize) -> usize {
+        let max = self.filter.max_degree.unwrap_or(self.target);
+        self.sides
+            .map_or(max, |sides| max.min(sides.degree(colour)))
+    }
+
+    /**
+     * The number of vertices still to be added which could be joined to a
+     * vertex of the given colour.
+     */
+    fn room(&self, g: &Partial, colour: usize) -> usize {
+        match self.sides {
+            None => self.target - g.len(),
+            Some(sides) => sides.order(1 - colour) - g.num_of_colour(1 - colour),
+        }
+    }
+
+    fn colours_to_add(&self, g: &Partial) -> Vec<usize> {
+        match self.sides {
+            None => vec![0],
+            Some(sides) => (0..2)
+                .filter(|c| g.num_of_colour(*c) < sides.order(*c))
+                .collect(),
+        }
+    }
+
+    /**
+     * Could child, just made by adding its last vertex, still grow into a
+     * graph we want? Only hereditary properties and degree counting are
+     * used, so the canonical parent of a graph we want always passes.
+     */
+    fn is_extension_allowed(&self, child: &Partial) -> bool {
+        let n = child.len();
+        let last = n - 1;
+        let new_deg = child.deg(last);
+        if (0..n).any(|v| child.deg(v) < new_deg) {
+            // The new vertex would not be the one to delete.
+            return false;
+        }
+        let mut deficits = [0; 2];
+        for v in 0..n {
+            let colour = child.colours[v];
+            let missing = self.min_degree(colour).saturating_sub(child.deg(v));
+            if missing > self.room(child, colour) {
+                return false;
+            }
+            deficits[colour] += missing;
+        }
+        // The missing edges at vertices of each colour must all go to
+        // vertices still to be added.
+        for (colour, deficit) in deficits.iter().enumerate() {
+            let other = if self.sides.is_some() { 1 - colour } else { 0 };
+            if *deficit > self.room(child, colour) * self.max_degree(other) {
+                return false;
+            }
+        }
+        if self.sides.is_none() {
+            // Conversely, each vertex still to be added gets at most one edge
+            // from each of the others, and the rest from the spare degree of
+            // the vertices we have.
+            let room = self.room(child, 0);
+            let spare: usize = (0..n)
+                .map(|v| self.max_degree(0).saturating_sub(child.deg(v)))
+                .sum();
+            if spare < room * self.min_degree(0).saturating_sub(room.saturating_sub(1)) {
+                return false;
+            }
+        }
+        let nbrs = child.adj[last];
+        if let Some(girth) = self.filter.min_girth {
+            // Any new cycle passes through the new vertex and two of its
+            // neighbours.
+            let parent = &child.adj[..last];
+            let mut rest = nbrs;
+            while rest != 0 {
+                let u = rest.trailing_zeros() as usize;
+                rest &= !bit(u);
+                if girth >= 3 && ball(parent, u, girth - 3) & rest != 0 {
+                    return false;
+                }
+            }
+        }
+        if self.filter.triangle_free
+            && (0..last).any(|v| nbrs & bit(v) != 0 && child.adj[v] & nbrs != 0)
+        {
+            return false;
+        }
+        true
+    }
+
+    /**
+     * A cheap invariant of a vertex, used to choose the vertex to delete
+     * without a canonical labelling whenever it is unique. Regular graphs
+     * agree on degrees, so short cycles and ball sizes do most of the work.
+     */
+    fn vertex_invariant(g: &Partial, v: usize) -> (usize, usize, usize, usize, usize) {
+        let nbrs = g.adj[v];
+        let mut nbr_degs = 0;
+        let mut twice_triangles = 0;
+        let mut squares = 0;
+        let mut rest = nbrs;
+        while rest != 0 {
+            let u = rest.trailing_zeros() as usize;
+            rest &= !bit(u);
+            nbr_degs += g.deg(u);
+            twice_triangles += (g.adj[u] & nbrs).count_ones() as usize;
+            let mut later = rest;
+            while later != 0 {
+                let w = later.trailing_zeros() as usize;
+                later &= !bit(w);
+                squares += (g.adj[u] & g.adj[w] & !bit(v)).count_ones() as usize;
+            }
+        }
+        let second_nbhd = ball(&g.adj, v, 2).count_ones() as usize;
+        let third_nbhd = ball(&g.adj, v, 3).count_ones() as usize;
+        (nbr_degs, twice_triangles, squares, second_nbhd, third_nbhd)
+    }
+
+    /**
+     * The vertex to delete from a graph is the one of minimum degree, then
+     * largest invariant, then largest canonical label. Returns the vertices
+     * tied with the last vertex of child before looking at the labels, or
+     * None if the last vertex is beaten already.
+     */
+    fn deletion_candidates(&self, child: &Partial) -> Option<Vec<usize>> {
+        let n = child.len();
+        let last = n - 1;
+        let min_deg = (0..n).map(|v| child.deg(v)).min().unwrap();
+        if child.deg(last) != min_deg {
+            return None;
+        }
+        let invariants: Vec<Option<_>> = (0..n)
+            .map(|v| (child.deg(v) == min_deg).then(|| Self::vertex_invariant(child, v)))
+            .collect();
+        let best = invariants.iter().max().unwrap();
+        if invariants[last] != *best {
+            return None;
+        }
+        Some((0..n).filter(|v| invariants[*v] == *best).collect())
+    }
+
+    /**
+     * Is the last vertex of child in the same orbit as the vertex to delete?
+     * Otherwise child is not to be accepted from this parent. Returns the
+     * generators of Aut(child) if they have been found on the way.
+     */
+    fn accepted_automorphisms(
+        &self,
+        child: &Partial,
+        ties: &[usize],
+        search: &mut CanonicalSearch,
+    ) -> Option<Option<Vec<Vec<usize>>>> {
+        let last = child.len() - 1;
+        if ties.len() == 1 {
+            return Some(None);
+        }
+        let lab = search.canonical_lab(&child.colours);
+        let chosen = *lab.iter().rev().find(|v| ties.contains(v)).unwrap();
+        if chosen == last {
+            return Some(None);
+        }
+        // The automorphisms found while labelling carry over, so this only
+        // has to find the rest of the group.
+        let generators = search.automorphism_group(&child.colours).0;
+        if is_in_orbit(chosen, last, &generators) {
+            Some(Some(generators))
+        } else {
+            None
+        }
+    }
+
+    fn is_output_allowed(&self, g: &Partial) -> bool {
+        (!self.filter.connected || is_connected(&g.adj))
+            && (0..g.len()).all(|v| g.deg(v) >= self.min_degree(g.colours[v]))
+    }
+
+    /**
+     * Returns true if f asked us to stop. The generators of Aut(g) are
+     * passed in if they are already known, and are otherwise only found,
+     * using the same search as labelled g, if there is more than one way to
+     * extend g.
+     */
+    fn extend(
+        &self,
+        g: &Partial,
+        mut generators: Option<Vec<Vec<usize>>>,
+        search: &mut CanonicalSearch,
+        f: &mut dyn FnMut(Graph) -> bool,
+    ) -> bool {
+        let k = g.len();
+        if k == self.target {
+            return self.is_output_allowed(g) && f(graph_of_bits(&g.adj));
+        }
+        for colour in self.colours_to_add(g) {
+            let mut candidates = 0;
+            for v in 0..k {
+                let other = g.colours[v];
+                if (self.sides.is_none() || other != colour) && g.deg(v) < self.max_degree(other) {
+                    candidates |= bit(v);
+                }
+            }
+            // The new vertex needs enough neighbours now to reach its
+            // minimum degree later, and can't have more than any other
+            // vertex, as it must be the one of minimum degree.
+            let grown = g.with_vertex(colour, 0);
+            let min_size = self
+                .min_degree(colour)
+                .saturating_sub(self.room(&grown, colour));
+            let max_size = (0..k)
+                .map(|v| g.deg(v) + 1)
+                .fold(self.max_degree(colour), usize::min);
+            // A vertex missing more edges than there will be vertices after
+            // this one must be joined to this one.
+            let mut forced = 0;
+            for v in 0..k {
+                let other = g.colours[v];
+                if candidates & bit(v) != 0
+                    && self.min_degree(other).saturating_sub(g.deg(v)) > self.room(&grown, other)
+                {
+                    forced |= bit(v);
+                }
+            }
+            let num_forced = forced.count_ones() as usize;
+            if num_forced > max_size {
+                continue;
+            }
+            // Two neighbourhoods in the same orbit of Aut(g) give the same
+            // child, and if two accepted children are isomorphic then their
+            // neighbourhoods are in the same orbit, so one neighbourhood
+            // from each orbit is all we need. Aut(g) fixes the forced
+            // vertices, so it permutes these options.
+            let options: Vec<u128> = subsets_of_sizes(
+                candidates & !forced,
+                min_size.saturating_sub(num_forced),
+                max_size - num_forced,
+            )
+            .map(|nbrs| nbrs | forced)
+            .collect();
+            let reps = if options.len() > 1 {
+                let generators =
+                    generators.get_or_insert_with(|| search.automorphism_group(&g.colours).0);
+                orbit_representatives(options.into_iter(), generators)
+            } else {
+                options
+            };
+            for nbrs in reps {
+                let child = g.with_vertex(colour, nbrs);
+                if !self.is_extension_allowed(&child)
+                    || (self.filter.bipartite && !is_bipartite(&child.adj))
+                {
+                    continue;
+                }
+                let Some(ties) = self.deletion_candidates(&child) else {
+                    continue;
+                };
+                let matrix = matrix_of_bits(&child.adj);
+                let mut child_search = CanonicalSearch::new(&matrix);
+                let Some(child_generators) =
+                    self.accepted_automorphisms(&child, &ties, &mut child_search)
+                else {
+                    continue;
+                };
+                if self.extend(&child, child_generators, &mut child_search, f) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+fn matrix_of_bits(adj: &[u128]) -> Vec<Vec<u32>> {
+    let n = adj.len();
+    adj.iter()
+        .map(|nbrs| (0..n).map(|u| ((nbrs >> u) & 1) as u32).collect())
+        .collect()
+}
+
+fn permute_bits(set: u128, gamma: &[usize]) -> u128 {
+    let mut image = 0;
+    let mut rest = set;
+    while rest != 0 {
+        let v = rest.trailing_zeros() as usize;
+        rest &= !bit(v);
+        image |= bit(gamma[v]);
+    }
+    image
+}
+
+/**
+ * The first of the subsets in each orbit of the group generated by
+ * generators, which must map the family of subsets to itself.
+ */
+fn orbit_representatives(
+    subsets: impl Iterator<Item = u128>,
+    generators: &[Vec<usize>],
+) -> Vec<u128> {
+    if generators.is_empty() {
+        return subsets.collect();
+    }
+    let mut seen = HashSet::new();
+    let mut reps = vec![];
+    for subset in subsets {
+        if !seen.insert(subset) {
+            continue;
+        }
+        reps.push(subset);
+        let mut stack = vec![subset];
+        while let Some(current) = stack.pop() {
+            for gamma in generators.iter() {
+                let image = permute_bits(current, gamma);
+                if seen.insert(image) {
+                    stack.push(image);
+                }
+            }
+        }
+    }
+    reps
+}
+
+/**
+ * Is v in the orbit of u under the group generated by generators?
+ */
+fn is_in_orbit(u: usize, v: usize, generators: &[Vec<usize>]) -> bool {
+    let mut seen = bit(u);
+    let mut stack = vec![u];
+    while let Some(x) = stack.pop() {
+        for gamma in generators.iter() {
+            if seen & bit(gamma[x]) == 0 {
+                seen |= bit(gamma[x]);
+                stack.push(gamma[x]);
+            }
+        }
+    }
+    seen & bit(v) != 0
+}
+
+fn graph_of_bits(adj: &[u128]) -> Graph {
+    let n = Order::of_usize(adj.len());
+    let mut matrix = VertexVec::new(n, &VertexVec::new(n, &false));
+    for (i, v) in n.iter_verts().enumerate() {
+        for (j, u) in n.iter_verts().enumerate() {
+            matrix[v][u] = adj[i] & bit(j) != 0;
+        }
+    }
+    let g = Graph::of_matrix(matrix, Constructor::Special);
+    let code = g.to_graph6();
+    Graph::of_matrix(g.adj, Constructor::Encoded(code))
+}
+
+fn run(
+    target: usize,
+    filter: &GraphFilter,
+    sides: Option<Biregular>,
+    f: &mut dyn FnMut(Graph) -> bool,
+) -> bool {
+    if target > 128 {
+        panic!("Can only generate graphs with at most 128 vertices!");
+    }
+    let generator = Generator {
+        target,
+        filter,
+        sides,
+    };
+    let empty = Partial {
+        adj: vec![],
+        colours: vec![],
+    };
+    generator.extend(&empty, None, &mut CanonicalSearch::new(&[]), f)
+}
+
+/**
+ * Calls f on every graph of the given order passing the filter, one from
+ * each isomorphism class, until f returns true. Returns whether f ever did.
+ */
+pub fn for_each_graph(
+    order: Order,
+    filter: &GraphFilter,
+    f: &mut dyn FnMut(Graph) -> bool,
+) -> bool {
+    run(order.to_usize(), filter, None, f)
+}
+
+/**
+ * As for_each_graph, but only for the degree-regular graphs.
+ */
+pub fn for_each_regular(
+    order: Order,
+    degree: Degree,
+    filter: &GraphFilter,
+    f: &mut dyn FnMut(Graph) -> bool,
+) -> bool {
+    let (n, d) = (order.to_usize(), degree.to_usize());
+    if d >= n.max(1) || (n * d) % 2 == 1 {
+        return false;
+    }
+    let mut filter = filter.to_owned();
+    filter.tighten_min_degree(Some(d));
+    filter.tighten_max_degree(Some(d));
+    run(n, &filter, None, f)
+}
+
+/**
+ * Calls f on every biregular graph with the given sides passing the
+ * filter, until f returns true. Graphs are taken up to isomorphisms which
+ * keep each side in place, so when the two sides look the same each graph
+ * may turn up twice, once the other way round.
+ */
+pub fn for_each_biregular(
+    sides: Biregular,
+    filter: &GraphFilter,
+    f: &mut dyn FnMut(Graph) -> bool,
+) -> bool {
+    if sides.left_order * sides.left_degree != sides.right_order * sides.right_degree
+        || sides.left_degree > sides.right_order
+        || sides.right_degree > sides.left_order
+    {
+        return false;
+    }
+    run(sides.left_order + sides.right_order, filter, Some(sides), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn count_graphs(order: Order, filter: &GraphFilter) -> usize {
+        let mut count = 0;
+        for_each_graph(order, filter, &mut |_| {
+            count += 1;
+            false
+        });
+        count
+    }
+
+    fn counts(filter: &GraphFilter, max_n: usize) -> Vec<usize> {
+        (1..=max_n)
+            .map(|n| count_graphs(Order::of_usize(n), filter))
+            .collect()
+    }
+
+    #[test]
+    fn test_count_all_graphs() {
+        assert_eq!(
+            counts(&GraphFilter::default(), 6),
+            vec![1, 2, 4, 11, 34, 156]
+        );
+    }
+
+    #[test]
+    fn test_count_connected_graphs() {
+        let filter = GraphFilter {
+            connected: true,
+            ..Default::default()
+        };
+        assert_eq!(counts(&filter, 6), vec![1, 1, 2, 6, 21, 112]);
+    }
+
+    #[test]
+    fn test_count_restricted_graphs() {
+        let triangle_free = GraphFilter {
+            triangle_free: true,
+            ..Default::default()
+        };
+        assert_eq!(count_graphs(Order::of_usize(6), &triangle_free), 38);
+
+        let bipartite = GraphFilter {
+            bipartite: true,
+            ..Default::default()
+        };
+        assert_eq!(counts(&bipartite, 6), vec![1, 2, 3, 7, 13, 35]);
+
+        let no_isolated = GraphFilter {
+            min_degree: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(counts(&no_isolated, 5), vec![0, 1, 2, 7, 23]);
+
+        let cubic = GraphFilter {
+            min_degree: Some(3),
+            max_degree: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(counts(&cubic, 8), vec![0, 0, 0, 1, 0, 2, 0, 6]);
+    }
+
+    #[test]
+    fn test_subsets_of_sizes() {
+        let set = 0b1011010110;
+        let found: Vec<u128> = subsets_of_sizes(set, 2, 3).collect();
+        assert_eq!(found.len(), 15 + 20);
+        assert!(found.iter().all(|x| x & !set == 0));
+        assert!(found.iter().all(|x| (2..=3).contains(&x.count_ones())));
+        assert_eq!(found.iter().collect::<HashSet<_>>().len(), found.len());
+        assert_eq!(subsets_of_sizes(set, 0, 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(subsets_of_sizes(set, 7, 9).count(), 0);
+    }
+
+    #[test]
+    fn test_filter_of_conditions() {
+        let conditions = vec![
+            BoolOperation::of_string_result("connected").unwrap(),
+            BoolOperation::of_string_result("max_deg <= 3").unwrap(),
+        ];
+        let filter = GraphFilter::of_conditions(&conditions);
+        assert!(filter.connected);
+        assert_eq!(filter.max_degree, Some(3));
+        assert_eq!(filter.min_degree, None);
+    }
+
+    fn count_regular(n: usize, d: usize, filter: &GraphFilter) -> usize {
+        let mut count = 0;
+        for_each_regular(Order::of_usize(n), Degree::of_usize(d), filter, &mut |g| {
+            assert!(g.deg.iter().all(|x| x.to_usize() == d));
+            count += 1;
+            false
+        });
+        count
+    }
+
+    #[test]
+    fn test_count_regular_graphs() {
+        let all = GraphFilter::default();
+        let connected = GraphFilter {
+            connected: true,
+            ..Default::default()
+        };
+        let cubic: Vec<usize> = (4..=12).map(|n| count_regular(n, 3, &all)).collect();
+        assert_eq!(cubic, vec![1, 0, 2, 0, 6, 0, 21, 0, 94]);
+        let cubic: Vec<usize> = (4..=12).map(|n| count_regular(n, 3, &connected)).collect();
+        assert_eq!(cubic, vec![1, 0, 2, 0, 5, 0, 19, 0, 85]);
+        let quartic: Vec<usize> = (5..=10).map(|n| count_regular(n, 4, &connected)).collect();
+        assert_eq!(quartic, vec![1, 1, 2, 6, 16, 59]);
+    }
+
+    #[test]
+    fn test_count_cubic_graphs_of_order_sixteen() {
+        // Slow enough to notice if the neighbourhoods stop being restricted
+        // to the vertices that still need degree.
+        let connected = GraphFilter {
+            connected: true,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        assert_eq!(count_regular(16, 3, &connected), 4060);
+        assert!(start.elapsed() < Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_count_high_girth_cubic_graphs() {
+        let girth_four = GraphFilter {
+            connected: true,
+            min_girth: Some(4),
+            ..Default::default()
+        };
+        let counts: Vec<usize> = (3..=6)
+            .map(|k| count_regular(2 * k, 3, &girth_four))
+            .collect();
+        assert_eq!(counts, vec![1, 2, 6, 22]);
+
+        let girth_five = GraphFilter {
+            min_girth: Some(5),
+            ..Default::default()
+        };
+        let mut found = vec![];
+        for_each_regular(
+            Order::of_usize(10),
+            Degree::of_usize(3),
+            &girth_five,
+            &mut |g| {
+                found.push(g);
+                false
+            },
+        );
+        assert_eq!(found.len(), 1);
+        let petersen = Constructor::of_string("petersen")
+            .new_entity()
+            .as_owned_graph();
+        assert!(found[0].is_isomorphic_to(&petersen));
+    }
+
+    #[test]
+    fn test_count_biregular_graphs() {
+        // Check against the bipartite graphs with the right degrees, in
+        // which every edge goes between the two sides.
+        for (left_order, left_degree, right_order, right_degree) in [
+            (2, 3, 3, 2),
+            (3, 2, 2, 3),
+            (4, 3, 6, 2),
+            (6, 2, 4, 3),
+            (3, 4, 6, 2),
+            (4, 2, 4, 2),
+        ] {
+            let sides = Biregular {
+                left_order,
+                left_degree,
+                right_order,
+                right_degree,
+            };
+            let mut count = 0;
+            for_each_biregular(sides, &GraphFilter::default(), &mut |g| {
+                assert_eq!(g.size(), left_order * left_degree);
+                count += 1;
+                false
+            });
+
+            let filter = GraphFilter {
+                bipartite: true,
+                min_degree: Some(left_degree.min(right_degree)),
+                max_degree: Some(left_degree.max(right_degree)),
+                ..Default::default()
+            };
+            let mut expected = 0;
+            let n = Order::of_usize(left_order + right_order);
+            for_each_graph(n, &filter, &mut |g| {
+                let num_left = g.deg.iter().filter(|d| d.to_usize() == left_degree).count();
+                let num_right = g
+                    .deg
+                    .iter()
+                    .filter(|d| d.to_usize() == right_degree)
+                    .count();
+                if left_degree != right_degree
+                    && num_left == left_order
+                    && num_right == right_order
+                    && g.iter_edges().all(|e| g.deg[e.fst()] != g.deg[e.snd()])
+                {
+                    expected += 1;
+                }
+                false
+            });
+            if left_degree != right_degree {
+                assert_eq!(count, expected, "{:?}", (left_order, right_order));
+            } else {
+                // 2-regular bipartite graphs on 4 + 4 vertices: C8 and two
+                // C4s.
+                assert_eq!(count, 2);
+            }
+        }
+    }
+}
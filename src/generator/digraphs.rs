@@ -0,0 +1,370 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::entity::canonical::*;
+use crate::entity::digraph::Digraph;
+use crate::operation::bool_operation::*;
+use crate::operation::int_operation::IntOperation;
+
+use utilities::vertex_tools::*;
+use utilities::*;
+
+use super::{bit, subsets};
+
+// Generation of tournaments and oriented graphs up to isomorphism by
+// canonical augmentation. A new vertex is added with every possible set of
+// arcs to the earlier ones, and is kept only if it is in the same orbit as
+// the vertex with the largest canonical label.
+
+#[derive(Clone, Debug, Default)]
+pub struct DigraphFilter {
+    pub strongly_connected: bool,
+    pub min_out_degree: Option<usize>,
+}
+
+impl DigraphFilter {
+    /**
+     * Picks out whichever of the conditions the generator can use. The
+     * conditions should still all be checked on the digraphs produced.
+     */
+    pub fn of_conditions(conditions: &[BoolOperation]) -> Self {
+        use BoolOperation::*;
+        use IntOperation::*;
+        use NumToBoolInfix::*;
+        let mut filter = Self::default();
+        for condition in conditions.iter() {
+            match condition {
+                IsConnected => filter.strongly_connected = true,
+                IntInfix(infix, MinOutDegree, Number(x)) => {
+                    let x = *x as usize;
+                    let lower = match infix {
+                        More => Some(x + 1),
+                        NotLess | Equal => Some(x),
+                        _ => None,
+                    };
+                    if let Some(bound) = lower {
+                        filter.min_out_degree = Some(bound.max(filter.min_out_degree.unwrap_or(0)));
+                    }
+                }
+                _ => (),
+            }
+        }
+        filter
+    }
+}
+
+impl fmt::Display for DigraphFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(d) = self.min_out_degree {
+            parts.push(format!("min out-degree >= {}", d));
+        }
+        if self.strongly_connected {
+            parts.push("strongly connected".to_owned());
+        }
+        if parts.is_empty() {
+            write!(f, "no restrictions")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/**
+ * The digraph built so far, as the out- and in-neighbourhoods of each
+ * vertex.
+ */
+#[derive(Clone)]
+struct Partial {
+    out_nbrs: Vec<u128>,
+    in_nbrs: Vec<u128>,
+}
+
+impl Partial {
+    fn len(&self) -> usize {
+        self.out_nbrs.len()
+    }
+
+    fn with_vertex(&self, out_nbrs: u128, in_nbrs: u128) -> Self {
+        let k = self.len();
+        let mut child = self.to_owned();
+        for v in 0..k {
+            if out_nbrs & bit(v) != 0 {
+                child.in_nbrs[v] |= bit(k);
+            }
+            if in_nbrs & bit(v) != 0 {
+                child.out_nbrs[v] |= bit(k);
+            }
+        }
+        child.out_nbrs.push(out_nbrs);
+        child.in_nbrs.push(in_nbrs);
+        child
+    }
+
+    fn matrix(&self) -> Vec<Vec<u32>> {
+        let n = self.len();
+        self.out_nbrs
+            .iter()
+            .map(|set| (0..n).map(|u| ((set >> u) & 1) as u32).collect())
+            .collect()
+    }
+
+    /**
+     * The vertices which can be reached from v by following arcs forwards
+     * (or backwards, if backwards is set).
+     */
+    fn reachable(&self, v: usize, backwards: bool) -> u128 {
+        let nbrs = if backwards {
+            &self.in_nbrs
+        } else {
+            &self.out_nbrs
+        };
+        let mut reached = bit(v);
+        let mut frontier = bit(v);
+        while frontier != 0 {
+            let u = frontier.trailing_zeros() as usize;
+            frontier &= !bit(u);
+            let new = nbrs[u] & !reached;
+            reached |= new;
+            frontier |= new;
+        }
+        reached
+    }
+
+    fn is_strongly_connected(&self) -> bool {
+        let everything = bit(self.len()) - 1;
+        self.len() == 0
+            || (self.reachable(0, false) == everything && self.reachable(0, true) == everything)
+    }
+}
+
+struct DigraphGenerator<'a> {
+    target: usize,
+    filter: &'a DigraphFilter,
+    tournament: bool,
+}
+
+impl<'a> DigraphGenerator<'a> {
+    /**
+     * Each vertex loses at most one out-neighbour whenever a vertex is
+     * deleted, so every ancestor of a digraph we want has out-degrees at
+     * least the bound minus the number of vertices still to come.
+     */
+    fn is_extension_allowed(&self, child: &Partial) -> bool {
+        match self.filter.min_out_degree {
+            Some(d) => {
+                let room = self.target - child.len();
+                child
+                    .out_nbrs
+                    .iter()
+                    .all(|set| set.count_ones() as usize + room >= d)
+            }
+            None => true,
+        }
+    }
+
+    /**
+     * If the last vertex of child is in the same orbit as the vertex with
+     * the largest canonical label, returns the canonical form of child.
+     */
+    fn canonical_if_accepted(&self, child: &Partial) -> Option<Vec<u128>> {
+        let n = child.len();
+        let last = n - 1;
+        let matrix = child.matrix();
+        let colours = vec![0; n];
+        let lab = canonical_lab(&matrix, &colours);
+        let chosen = lab[n - 1];
+        if chosen != last {
+            let orbits = AutomorphismGroup::of_matrix(&matrix, &colours).vertex_orbits();
+            if orbits.get_component(Vertex::of_usize(chosen))
+                != orbits.get_component(Vertex::of_usize(last))
+            {
+                return None;
+            }
+        }
+        let mut label = vec![0; n];
+        for (i, v) in lab.iter().enumerate() {
+            label[*v] = i;
+        }
+        let mut canonical = vec![0; n];
+        for (v, set) in child.out_nbrs.iter().enumerate() {
+            for u in 0..n {
+                if set & bit(u) != 0 {
+                    canonical[label[v]] |= bit(label[u]);
+                }
+            }
+        }
+        Some(canonical)
+    }
+
+    fn is_output_allowed(&self, d: &Partial) -> bool {
+        !self.filter.strongly_connected || d.is_strongly_connected()
+    }
+
+    /**
+     * All the ways of joining a new vertex to the k vertices so far, as
+     * pairs of out- and in-neighbourhoods.
+     */
+    fn arc_sets(&self, k: usize) -> Vec<(u128, u128)> {
+        let everything = bit(k) - 1;
+        let mut arc_sets = vec![];
+        for out_nbrs in subsets(everything) {
+            if self.tournament {
+                arc_sets.push((out_nbrs, everything & !out_nbrs));
+            } else {
+                for in_nbrs in subsets(everything & !out_nbrs) {
+                    arc_sets.push((out_nbrs, in_nbrs));
+                }
+            }
+        }
+        arc_sets
+    }
+
+    /**
+     * Returns true if f asked us to stop.
+     */
+    fn extend(&self, d: &Partial, f: &mut dyn FnMut(Digraph) -> bool) -> bool {
+        let k = d.len();
+        if k == self.target {
+            return self.is_output_allowed(d) && f(digraph_of_bits(&d.out_nbrs));
+        }
+        let mut children: HashSet<Vec<u128>> = HashSet::new();
+        for (out_nbrs, in_nbrs) in self.arc_sets(k) {
+            let child = d.with_vertex(out_nbrs, in_nbrs);
+            if !self.is_extension_allowed(&child) {
+                continue;
+            }
+            if let Some(canonical) = self.canonical_if_accepted(&child) {
+                if children.insert(canonical) && self.extend(&child, f) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+fn digraph_of_bits(out_nbrs: &[u128]) -> Digraph {
+    let n = Order::of_usize(out_nbrs.len());
+    let mut adj = VertexVec::new(n, &VertexVec::new(n, &false));
+    for (i, v) in n.iter_verts().enumerate() {
+        for (j, u) in n.iter_verts().enumerate() {
+            adj[v][u] = out_nbrs[i] & bit(j) != 0;
+        }
+    }
+    Digraph::of_matrix(adj, vec![])
+}
+
+fn run(
+    order: Order,
+    filter: &DigraphFilter,
+    tournament: bool,
+    f: &mut dyn FnMut(Digraph) -> bool,
+) -> bool {
+    let target = order.to_usize();
+    if target > 128 {
+        panic!("Can only generate digraphs with at most 128 vertices!");
+    }
+    let generator = DigraphGenerator {
+        target,
+        filter,
+        tournament,
+    };
+    let empty = Partial {
+        out_nbrs: vec![],
+        in_nbrs: vec![],
+    };
+    generator.extend(&empty, f)
+}
+
+/**
+ * Calls f on every tournament of the given order passing the filter, one
+ * from each isomorphism class, until f returns true. Returns whether f
+ * ever did.
+ */
+pub fn for_each_tournament(
+    order: Order,
+    filter: &DigraphFilter,
+    f: &mut dyn FnMut(Digraph) -> bool,
+) -> bool {
+    run(order, filter, true, f)
+}
+
+/**
+ * As for_each_tournament, but for oriented graphs, i.e. digraphs with at
+ * most one arc between any two vertices.
+ */
+pub fn for_each_oriented_graph(
+    order: Order,
+    filter: &DigraphFilter,
+    f: &mut dyn FnMut(Digraph) -> bool,
+) -> bool {
+    run(order, filter, false, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(n: usize, filter: &DigraphFilter, tournament: bool) -> usize {
+        let mut count = 0;
+        run(Order::of_usize(n), filter, tournament, &mut |_| {
+            count += 1;
+            false
+        });
+        count
+    }
+
+    #[test]
+    fn test_count_tournaments() {
+        let counts: Vec<usize> = (1..=7)
+            .map(|n| count(n, &DigraphFilter::default(), true))
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2, 4, 12, 56, 456]);
+
+        let strong = DigraphFilter {
+            strongly_connected: true,
+            ..Default::default()
+        };
+        let counts: Vec<usize> = (1..=7).map(|n| count(n, &strong, true)).collect();
+        assert_eq!(counts, vec![1, 0, 1, 1, 6, 35, 353]);
+    }
+
+    #[test]
+    fn test_count_oriented_graphs() {
+        let counts: Vec<usize> = (1..=5)
+            .map(|n| count(n, &DigraphFilter::default(), false))
+            .collect();
+        assert_eq!(counts, vec![1, 2, 7, 42, 582]);
+    }
+
+    #[test]
+    fn test_min_out_degree() {
+        // Compare the pruned search with checking every digraph.
+        for tournament in [true, false] {
+            let filter = DigraphFilter {
+                min_out_degree: Some(2),
+                ..Default::default()
+            };
+            let mut expected = 0;
+            run(
+                Order::of_usize(5),
+                &DigraphFilter::default(),
+                tournament,
+                &mut |d| {
+                    if d.out_deg.iter().all(|deg| deg.to_usize() >= 2) {
+                        expected += 1;
+                    }
+                    false
+                },
+            );
+            assert_eq!(count(5, &filter, tournament), expected);
+        }
+        // There is just one regular tournament on five vertices.
+        let regular = DigraphFilter {
+            min_out_degree: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(count(5, &regular, true), 1);
+    }
+}
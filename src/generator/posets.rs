@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::constructor::*;
+use crate::entity::canonical::*;
+use crate::entity::digraph::Digraph;
+use crate::entity::poset::Poset;
+use crate::operation::bool_operation::*;
+use crate::operation::int_operation::IntOperation;
+
+use utilities::vertex_tools::*;
+use utilities::*;
+
+use super::bit;
+
+// Generation of posets up to isomorphism by canonical augmentation. Each
+// poset is built by adding a new maximal element on top of a downset of a
+// smaller one, and is kept only if the new element is in the same orbit as
+// the maximal element with the largest canonical label.
+
+#[derive(Clone, Debug, Default)]
+pub struct PosetFilter {
+    pub connected: bool,
+    pub max_height: Option<usize>,
+    pub twin_free: bool,
+}
+
+impl PosetFilter {
+    /**
+     * Picks out whichever of the conditions the generator can use. The
+     * conditions should still all be checked on the posets produced.
+     */
+    pub fn of_conditions(conditions: &[BoolOperation]) -> Self {
+        use BoolOperation::*;
+        use IntOperation::*;
+        use NumToBoolInfix::*;
+        let mut filter = Self::default();
+        for condition in conditions.iter() {
+            match condition {
+                IsConnected => filter.connected = true,
+                Not(op) if matches!(**op, HasTwinElements) => filter.twin_free = true,
+                IntInfix(infix, Height, Number(x)) => {
+                    let x = *x as usize;
+                    let upper = match infix {
+                        Less => Some(x.saturating_sub(1)),
+                        NotMore | Equal => Some(x),
+                        _ => None,
+                    };
+                    if let Some(bound) = upper {
+                        filter.max_height = Some(filter.max_height.map_or(bound, |h| h.min(bound)));
+                    }
+                }
+                _ => (),
+            }
+        }
+        filter
+    }
+}
+
+impl fmt::Display for PosetFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(h) = self.max_height {
+            parts.push(format!("height <= {}", h));
+        }
+        if self.connected {
+            parts.push("connected".to_owned());
+        }
+        if self.twin_free {
+            parts.push("no twins".to_owned());
+        }
+        if parts.is_empty() {
+            write!(f, "no restrictions")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/**
+ * The poset built so far: below[v] is the set of elements less than v,
+ * and heights[v] the number of elements in the longest chain with top v.
+ */
+#[derive(Clone)]
+struct Partial {
+    below: Vec<u128>,
+    heights: Vec<usize>,
+}
+
+impl Partial {
+    fn len(&self) -> usize {
+        self.below.len()
+    }
+
+    fn is_downset(&self, set: u128) -> bool {
+        (0..self.len()).all(|v| set & bit(v) == 0 || self.below[v] & !set == 0)
+    }
+
+    fn with_maximal(&self, below: u128) -> Self {
+        let height = (0..self.len())
+            .filter(|v| below & bit(*v) != 0)
+            .map(|v| self.heights[v])
+            .max()
+            .unwrap_or(0);
+        let mut child = self.to_owned();
+        child.below.push(below);
+        child.heights.push(height + 1);
+        child
+    }
+
+    fn is_maximal(&self, v: usize) -> bool {
+        self.below.iter().all(|set| set & bit(v) == 0)
+    }
+
+    fn matrix(&self) -> Vec<Vec<u32>> {
+        let n = self.len();
+        self.below
+            .iter()
+            .map(|set| (0..n).map(|u| ((set >> u) & 1) as u32).collect())
+            .collect()
+    }
+
+    fn is_connected(&self) -> bool {
+        let n = self.len();
+        let mut reached: u128 = 1;
+        loop {
+            let mut next = reached;
+            for v in 0..n {
+                if reached & bit(v) != 0 {
+                    next |= self.below[v];
+                } else if self.below[v] & reached != 0 {
+                    next |= bit(v);
+                }
+            }
+            if next == reached {
+                return reached.count_ones() as usize == n;
+            }
+            reached = next;
+        }
+    }
+
+    fn covers(&self, v: usize) -> u128 {
+        let mut covers = self.below[v];
+        for u in 0..self.len() {
+            if self.below[v] & bit(u) != 0 {
+                covers &= !self.below[u];
+            }
+        }
+        covers
+    }
+
+    /**
+     * Are there two elements with the same upper and lower covers?
+     */
+    fn has_twins(&self) -> bool {
+        let n = self.len();
+        let lower: Vec<u128> = (0..n).map(|v| self.covers(v)).collect();
+        let upper: Vec<u128> = (0..n)
+            .map(|v| {
+                (0..n)
+                    .filter(|u| lower[*u] & bit(v) != 0)
+                    .fold(0, |set, u| set | bit(u))
+            })
+            .collect();
+        (0..n).any(|u| (0..u).any(|v| lower[u] == lower[v] && upper[u] == upper[v]))
+    }
+}
+
+struct PosetGenerator<'a> {
+    target: usize,
+    filter: &'a PosetFilter,
+}
+
+impl<'a> PosetGenerator<'a> {
+    /**
+     * If the last element of child is in the same orbit as the maximal
+     * element with the largest canonical label, returns the canonical form
+     * of child.
+     */
+    fn canonical_if_accepted(&self, child: &Partial) -> Option<Vec<u128>> {
+        let n = child.len();
+        let last = n - 1;
+        let matrix = child.matrix();
+        let colours = vec![0; n];
+        let lab = canonical_lab(&matrix, &colours);
+        let chosen = *lab.iter().rev().find(|v| child.is_maximal(**v)).unwrap();
+        if chosen != last {
+            let orbits = AutomorphismGroup::of_matrix(&matrix, &colours).vertex_orbits();
+            if orbits.get_component(Vertex::of_usize(chosen))
+                != orbits.get_component(Vertex::of_usize(last))
+            {
+                return None;
+            }
+        }
+        let mut label = vec![0; n];
+        for (i, v) in lab.iter().enumerate() {
+            label[*v] = i;
+        }
+        let mut canonical = vec![0; n];
+        for (v, set) in child.below.iter().enumerate() {
+            for u in 0..n {
+                if set & bit(u) != 0 {
+                    canonical[label[v]] |= bit(label[u]);
+                }
+            }
+        }
+        Some(canonical)
+    }
+
+    fn is_output_allowed(&self, p: &Partial) -> bool {
+        (!self.filter.connected || p.is_connected()) && (!self.filter.twin_free || !p.has_twins())
+    }
+
+    /**
+     * Returns true if f asked us to stop.
+     */
+    fn extend(&self, p: &Partial, f: &mut dyn FnMut(Poset) -> bool) -> bool {
+        let k = p.len();
+        if k == self.target {
+            return self.is_output_allowed(p) && f(poset_of_bits(&p.below));
+        }
+        let mut children: HashSet<Vec<u128>> = HashSet::new();
+        for below in 0..bit(k) {
+            if !p.is_downset(below) {
+                continue;
+            }
+            let child = p.with_maximal(below);
+            if self.filter.max_height.is_some_and(|h| child.heights[k] > h) {
+                continue;
+            }
+            if let Some(canonical) = self.canonical_if_accepted(&child) {
+                if children.insert(canonical) && self.extend(&child, f) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+fn poset_of_bits(below: &[u128]) -> Poset {
+    let n = Order::of_usize(below.len());
+    let mut gt = VertexVec::new(n, &VertexVec::new(n, &false));
+    for (i, v) in n.iter_verts().enumerate() {
+        for (j, u) in n.iter_verts().enumerate() {
+            gt[v][u] = below[i] & bit(j) != 0;
+        }
+    }
+    let code = Digraph::of_matrix(gt.to_owned(), vec![]).to_digraph6();
+    Poset::of_ordering(
+        gt,
+        Constructor::PosetConstr(PosetConstructor::OfDigraph6(code)),
+    )
+}
+
+/**
+ * Calls f on every poset of the given order passing the filter, one from
+ * each isomorphism class, until f returns true. Returns whether f ever did.
+ */
+pub fn for_each_poset(
+    order: Order,
+    filter: &PosetFilter,
+    f: &mut dyn FnMut(Poset) -> bool,
+) -> bool {
+    let target = order.to_usize();
+    if target > 127 {
+        panic!("Can only generate posets with at most 127 elements!");
+    }
+    let generator = PosetGenerator { target, filter };
+    let empty = Partial {
+        below: vec![],
+        heights: vec![],
+    };
+    generator.extend(&empty, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dossier::*;
+    use crate::entity::Entity;
+
+    fn count(n: usize, filter: &PosetFilter) -> usize {
+        let mut count = 0;
+        for_each_poset(Order::of_usize(n), filter, &mut |_| {
+            count += 1;
+            false
+        });
+        count
+    }
+
+    #[test]
+    fn test_count_posets() {
+        let counts: Vec<usize> = (1..=7).map(|n| count(n, &PosetFilter::default())).collect();
+        assert_eq!(counts, vec![1, 2, 5, 16, 63, 318, 2045]);
+
+        let connected = PosetFilter {
+            connected: true,
+            ..Default::default()
+        };
+        let counts: Vec<usize> = (1..=7).map(|n| count(n, &connected)).collect();
+        assert_eq!(counts, vec![1, 1, 3, 10, 44, 238, 1650]);
+    }
+
+    #[test]
+    fn test_filtered_posets() {
+        // Compare the pruned searches with checking every poset.
+        let short = PosetFilter {
+            max_height: Some(2),
+            ..Default::default()
+        };
+        let twin_free = PosetFilter {
+            twin_free: true,
+            ..Default::default()
+        };
+        let mut num_short = 0;
+        let mut num_twin_free = 0;
+        for_each_poset(Order::of_usize(6), &PosetFilter::default(), &mut |p| {
+            if p.height <= 2 {
+                num_short += 1;
+            }
+            let mut dossier = Dossier::new(Entity::Poset(p));
+            let mut ann_box = AnnotationsBox::new();
+            let has_twins = BoolOperation::HasTwinElements;
+            if !dossier.operate_bool(&mut ann_box, &has_twins) {
+                num_twin_free += 1;
+            }
+            false
+        });
+        assert_eq!(count(6, &short), num_short);
+        assert_eq!(count(6, &twin_free), num_twin_free);
+    }
+
+    #[test]
+    fn test_posets_round_trip() {
+        for_each_poset(Order::of_usize(5), &PosetFilter::default(), &mut |p| {
+            let code = match &p.constructor {
+                Constructor::PosetConstr(PosetConstructor::OfDigraph6(code)) => code.to_owned(),
+                _ => panic!("Generated posets should come with their codes!"),
+            };
+            let q = Constructor::of_string(&format!("poset({})", code)).new_entity();
+            let gt = q.as_poset().gt.to_owned();
+            assert_eq!(Digraph::of_matrix(gt, vec![]).to_digraph6(), code);
+            assert_eq!(q.as_poset().height, p.height);
+            false
+        });
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+
+use crate::constructor::Constructor;
+use crate::entity::graph::Graph;
+use crate::operation::bool_operation::*;
+use crate::operation::int_operation::IntOperation;
+
+// Generation of trees up to isomorphism through their level sequences,
+// i.e. the depths of the vertices in preorder. Rooted trees come from
+// Beyer and Hedetniemi's successor function, and free trees from Wright,
+// Richmond, Odlyzko and McKay's adaptation of it to trees rooted at their
+// centres. Both take constant amortised time per tree.
+
+#[derive(Clone, Debug, Default)]
+pub struct TreeFilter {
+    pub max_degree: Option<usize>,
+    pub max_diameter: Option<usize>,
+}
+
+impl TreeFilter {
+    /**
+     * Picks out bounds on the max degree and diameter from the conditions.
+     * The conditions should still all be checked on the trees produced.
+     */
+    pub fn of_conditions(conditions: &[BoolOperation]) -> Self {
+        use BoolOperation::*;
+        use IntOperation::*;
+        use NumToBoolInfix::*;
+        let mut filter = Self::default();
+        for condition in conditions.iter() {
+            if let IntInfix(infix, op, Number(x)) = condition {
+                let x = *x as usize;
+                let upper = match infix {
+                    Less => Some(x.saturating_sub(1)),
+                    NotMore | Equal => Some(x),
+                    _ => None,
+                };
+                if let Some(bound) = upper {
+                    match op {
+                        MaxDegree => filter.max_degree = Some(bound),
+                        Diameter => filter.max_diameter = Some(bound),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        filter
+    }
+
+    fn allows(&self, levels: &[usize]) -> bool {
+        let parents = parents_of_levels(levels);
+        if let Some(max_degree) = self.max_degree {
+            let mut degrees = vec![0; levels.len()];
+            for (i, parent) in parents.iter().enumerate() {
+                degrees[i + 1] += 1;
+                degrees[*parent] += 1;
+            }
+            if degrees.iter().any(|d| *d > max_degree) {
+                return false;
+            }
+        }
+        if let Some(max_diameter) = self.max_diameter {
+            // Longest path down from each vertex, and the longest path
+            // overall, found by going through the vertices backwards.
+            let mut heights = vec![0; levels.len()];
+            let mut diameter = 0;
+            for (i, parent) in parents.iter().enumerate().rev() {
+                let through = heights[*parent] + heights[i + 1] + 1;
+                diameter = diameter.max(through);
+                heights[*parent] = heights[*parent].max(heights[i + 1] + 1);
+            }
+            if diameter > max_diameter {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl fmt::Display for TreeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(d) = self.max_degree {
+            parts.push(format!("max degree <= {}", d));
+        }
+        if let Some(d) = self.max_diameter {
+            parts.push(format!("diameter <= {}", d));
+        }
+        if parts.is_empty() {
+            write!(f, "no restrictions")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/**
+ * parents[i] is the parent of vertex i + 1, as used by RootedTree.
+ */
+fn parents_of_levels(levels: &[usize]) -> Vec<usize> {
+    let mut last_at_level = vec![0; levels.len()];
+    let mut parents = Vec::with_capacity(levels.len().saturating_sub(1));
+    for (i, level) in levels.iter().enumerate() {
+        if i > 0 {
+            parents.push(last_at_level[level - 1]);
+        }
+        last_at_level[*level] = i;
+    }
+    parents
+}
+
+fn tree_of_levels(levels: &[usize]) -> Graph {
+    Constructor::RootedTree(parents_of_levels(levels))
+        .new_entity()
+        .as_owned_graph()
+}
+
+/**
+ * The next level sequence of a rooted tree after levels, changing the
+ * sequence from position p onwards. Returns false if there isn't one.
+ */
+fn next_rooted_from(levels: &mut [usize], p: usize) -> bool {
+    if p == 0 {
+        return false;
+    }
+    let mut q = p - 1;
+    while levels[q] != levels[p] - 1 {
+        q -= 1;
+    }
+    for i in p..levels.len() {
+        levels[i] = levels[i - p + q];
+    }
+    true
+}
+
+fn next_rooted(levels: &mut [usize]) -> bool {
+    match levels.iter().rposition(|level| *level > 1) {
+        Some(p) => next_rooted_from(levels, p),
+        None => false,
+    }
+}
+
+/**
+ * Splits the tree into the first subtree of the root (with levels reduced
+ * by one) and the rest.
+ */
+fn split_tree(levels: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let m = levels
+        .iter()
+        .enumerate()
+        .skip(2)
+        .find(|(_, level)| **level == 1)
+        .map_or(levels.len(), |(i, _)| i);
+    let left = levels[1..m].iter().map(|level| level - 1).collect();
+    let mut rest = vec![0];
+    rest.extend_from_slice(&levels[m..]);
+    (left, rest)
+}
+
+/**
+ * Moves levels on to the first sequence, at or after it, of a free tree
+ * rooted at its centre with its tallest subtree first.
+ */
+fn next_free(levels: &mut [usize]) -> bool {
+    let (left, rest) = split_tree(levels);
+    let left_height = *left.iter().max().unwrap();
+    let rest_height = *rest.iter().max().unwrap();
+    let is_valid = rest_height > left_height
+        || (rest_height == left_height
+            && (left.len() < rest.len() || (left.len() == rest.len() && left <= rest)));
+    if is_valid {
+        return true;
+    }
+    let p = left.len();
+    let needs_fix = levels[p] > 2;
+    if !next_rooted_from(levels, p) {
+        return false;
+    }
+    if needs_fix {
+        let (new_left, _) = split_tree(levels);
+        let new_left_height = *new_left.iter().max().unwrap();
+        let n = levels.len();
+        for (i, level) in (1..=(new_left_height + 1)).enumerate() {
+            levels[n - (new_left_height + 1) + i] = level;
+        }
+    }
+    true
+}
+
+/**
+ * Calls f on every rooted tree of order n passing the filter, one from
+ * each isomorphism class, rooted at vertex 0, until f returns true.
+ * Returns whether f ever did.
+ */
+pub fn for_each_rooted_tree(
+    n: usize,
+    filter: &TreeFilter,
+    f: &mut dyn FnMut(Graph) -> bool,
+) -> bool {
+    if n == 0 {
+        return false;
+    }
+    let mut levels: Vec<usize> = (0..n).collect();
+    loop {
+        if filter.allows(&levels) && f(tree_of_levels(&levels)) {
+            return true;
+        }
+        if !next_rooted(&mut levels) {
+            return false;
+        }
+    }
+}
+
+/**
+ * Calls f on every free tree of order n passing the filter, one from each
+ * isomorphism class, until f returns true. Returns whether f ever did.
+ */
+pub fn for_each_free_tree(n: usize, filter: &TreeFilter, f: &mut dyn FnMut(Graph) -> bool) -> bool {
+    if n <= 2 {
+        return n > 0 && f(tree_of_levels(&(0..n).collect::<Vec<usize>>()));
+    }
+    // Start at the path, rooted at its centre.
+    let mut levels: Vec<usize> = (0..=(n / 2)).chain(1..n.div_ceil(2)).collect();
+    loop {
+        if !next_free(&mut levels) {
+            return false;
+        }
+        if filter.allows(&levels) && f(tree_of_levels(&levels)) {
+            return true;
+        }
+        if !next_rooted(&mut levels) {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn count(n: usize, filter: &TreeFilter, rooted: bool) -> usize {
+        let mut count = 0;
+        let mut f = |_| {
+            count += 1;
+            false
+        };
+        if rooted {
+            for_each_rooted_tree(n, filter, &mut f);
+        } else {
+            for_each_free_tree(n, filter, &mut f);
+        }
+        count
+    }
+
+    #[test]
+    fn test_count_free_trees() {
+        let counts: Vec<usize> = (1..=16)
+            .map(|n| count(n, &TreeFilter::default(), false))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![1, 1, 1, 2, 3, 6, 11, 23, 47, 106, 235, 551, 1301, 3159, 7741, 19320]
+        );
+    }
+
+    #[test]
+    fn test_count_rooted_trees() {
+        let counts: Vec<usize> = (1..=10)
+            .map(|n| count(n, &TreeFilter::default(), true))
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2, 4, 9, 20, 48, 115, 286, 719]);
+    }
+
+    #[test]
+    fn test_free_trees_distinct() {
+        let mut forms = HashSet::new();
+        for_each_free_tree(10, &TreeFilter::default(), &mut |t| {
+            assert_eq!(t.size(), 9);
+            assert!(t.is_connected());
+            assert!(forms.insert(t.canonical_form()));
+            false
+        });
+        assert_eq!(forms.len(), 106);
+    }
+
+    #[test]
+    fn test_filtered_trees() {
+        // Trees with max degree 3 (A000672) and trees of diameter at most 3.
+        let subcubic = TreeFilter {
+            max_degree: Some(3),
+            ..Default::default()
+        };
+        let counts: Vec<usize> = (1..=12).map(|n| count(n, &subcubic, false)).collect();
+        assert_eq!(counts, vec![1, 1, 1, 2, 2, 4, 6, 11, 18, 37, 66, 135]);
+
+        let short = TreeFilter {
+            max_diameter: Some(3),
+            ..Default::default()
+        };
+        // Stars and double stars.
+        assert_eq!(count(10, &short, false), 5);
+    }
+}
@@ -7,33 +7,53 @@ mod constructor;
 mod controller;
 mod dossier;
 mod entity;
+mod generator;
 mod operation;
 mod pattern;
 
 use controller::*;
 
-fn execute_instruction(text: &String) {
+/**
+ * Progress messages go to stderr when stdout is reserved for output.
+ */
+fn report(controller: &Controller, message: &str) {
+    if controller.writes_to_stdout() {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+fn execute_instruction(text: &String, is_direct: bool) {
     let start_time = SystemTime::now();
     let controller = Controller::of_string(text);
-    println!(
-        "Instruction constructed!\n{},\nTime: {}",
-        controller,
-        start_time.elapsed().unwrap().as_millis()
+    if is_direct {
+        report(&controller, "Argument received, running directly!");
+    }
+    report(
+        &controller,
+        &format!(
+            "Instruction constructed!\n{},\nTime: {}",
+            controller,
+            start_time.elapsed().unwrap().as_millis()
+        ),
     );
 
     controller.execute();
-    println!(
-        "Finished! Time (s): {}",
-        start_time.elapsed().unwrap().as_secs()
-    )
+    report(
+        &controller,
+        &format!(
+            "Finished! Time (s): {}",
+            start_time.elapsed().unwrap().as_secs()
+        ),
+    );
 }
 
 fn main() {
     env::set_var("RUST_BACKTRACE", "1");
     let args: Vec<String> = env::args().collect();
     if args.len() > 1 {
-        println!("Argument received, running directly!");
-        execute_instruction(&args[1])
+        execute_instruction(&args[1], true)
     } else {
         loop {
             println!("Enter instruction:");
@@ -41,7 +61,7 @@ fn main() {
             io::stdin()
                 .read_line(&mut text)
                 .expect("Failed to read line");
-            execute_instruction(&text)
+            execute_instruction(&text, false)
         }
     }
 }
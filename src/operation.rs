@@ -2,12 +2,15 @@ use std::fmt;
 
 pub mod bool_operation;
 pub mod int_operation;
+pub mod polynomial_operation;
 pub mod rational_operation;
 pub mod string_list_operation;
+pub mod subgraph_pattern;
 pub mod unit_operation;
 
 use bool_operation::*;
 use int_operation::*;
+use polynomial_operation::*;
 use rational_operation::*;
 use string_list_operation::*;
 use unit_operation::*;
@@ -18,6 +21,7 @@ pub enum Operation {
     Rational(RationalOperation),
     Unit(UnitOperation),
     StringList(StringListOperation),
+    Polynomial(PolynomialOperation),
 }
 
 impl Operation {
@@ -28,6 +32,7 @@ impl Operation {
             .or_else(|| RationalOperation::of_string_result(text).map(Self::Rational))
             .or_else(|| UnitOperation::of_string_result(text).map(Self::Unit))
             .or_else(|| StringListOperation::of_string_result(text).map(Self::StringList))
+            .or_else(|| PolynomialOperation::of_string_result(text).map(Self::Polynomial))
             .unwrap_or_else(|| panic!("Unknown operation {}", text))
     }
 }
@@ -41,6 +46,7 @@ impl fmt::Display for Operation {
             Rational(op) => write!(f, "{}", op),
             Unit(op) => write!(f, "{}", op),
             StringList(op) => write!(f, "{}", op),
+            Polynomial(op) => write!(f, "{}", op),
         }
     }
 }
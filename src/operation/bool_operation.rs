@@ -2,6 +2,8 @@ use std::fmt;
 use utilities::*;
 
 use crate::operation::int_operation::*;
+use crate::operation::polynomial_operation::*;
+use crate::operation::subgraph_pattern::*;
 
 use super::rational_operation::RationalOperation;
 
@@ -34,6 +36,9 @@ pub enum BoolOperation {
     HasLongMonotone,
     HasIntervalColoring,
     IsPlanar,
+    IsHamiltonian,
+    IsTraceable,
+    IsHamiltonConnected,
     IsRegular,
     BunkbedDiffsAllUnimodal,
     HasRegularLosslessEdgeDominator,
@@ -90,6 +95,11 @@ pub enum BoolOperation {
     HasFourCycle,
     IsGenericallyHyperbolicEmbeddable(bool),
     HasFewEdgesHyperbolic,
+    ContainsSubgraph(SubgraphPattern),
+    ContainsInduced(SubgraphPattern),
+    IsUnimodal(PolynomialOperation),
+    IsLogConcave(PolynomialOperation),
+    IsRealRooted(PolynomialOperation),
     Debug,
 }
 
@@ -152,7 +162,6 @@ impl BoolOperation {
             }
             None => {
                 let (func, args) = parse_function_like(text);
-                println!("PARSING: func: {}, args: {:?}", func, args);
                 match func.trim().to_lowercase().as_str() {
                     "not" | "!" | "¬" => {
                         Self::of_string_result(args[0]).map(|op| Not(Box::new(op)))
@@ -164,6 +173,9 @@ impl BoolOperation {
                     "has_long_monotone" | "has_monot" | "is_monot" => Some(HasLongMonotone),
                     "has_interval_coloring" | "has_interval" => Some(HasIntervalColoring),
                     "is_planar" | "planar" => Some(IsPlanar),
+                    "is_hamiltonian" | "hamiltonian" => Some(IsHamiltonian),
+                    "is_traceable" | "traceable" => Some(IsTraceable),
+                    "is_hamilton_connected" | "hamilton_connected" => Some(IsHamiltonConnected),
                     "is_regular" | "regular" => Some(IsRegular),
                     "bunkbed_all_unimodal" => Some(BunkbedDiffsAllUnimodal),
                     "has_regular_lossless_edge_dominator" | "has_rled" => {
@@ -235,6 +247,7 @@ impl BoolOperation {
                         Some(IsPosetIncomparabilityConnected)
                     }
                     "has_twins" => Some(HasTwinElements),
+                    "twin_free" => Some(Not(Box::new(HasTwinElements))),
                     "has_almost_twins" => Some(HasAlmostTwinElements),
                     "has_non_maximal_almost_twins" => Some(HasAlmostTwinElementsIgnoreMaximal),
                     "num_extensions_less_than" => {
@@ -259,6 +272,27 @@ impl BoolOperation {
                         args.first()?.parse().unwrap_or(false),
                     )),
                     "few_edges_h" => Some(HasFewEdgesHyperbolic),
+                    "contains_subgraph" | "contains" => {
+                        Some(ContainsSubgraph(SubgraphPattern::of_string(args.first()?)))
+                    }
+                    "contains_induced" => {
+                        Some(ContainsInduced(SubgraphPattern::of_string(args.first()?)))
+                    }
+                    "subgraph_free" => Some(Not(Box::new(ContainsSubgraph(
+                        SubgraphPattern::of_string(args.first()?),
+                    )))),
+                    "induced_free" => Some(Not(Box::new(ContainsInduced(
+                        SubgraphPattern::of_string(args.first()?),
+                    )))),
+                    "unimodal" | "is_unimodal" => {
+                        PolynomialOperation::of_string_result(args[0]).map(IsUnimodal)
+                    }
+                    "log_concave" | "is_log_concave" => {
+                        PolynomialOperation::of_string_result(args[0]).map(IsLogConcave)
+                    }
+                    "real_rooted" | "is_real_rooted" => {
+                        PolynomialOperation::of_string_result(args[0]).map(IsRealRooted)
+                    }
                     "debug" => Some(Debug),
                     &_ => None,
                 }
@@ -325,6 +359,9 @@ impl fmt::Display for BoolOperation {
             HasLongMonotone => "Has 1->n monotone path".to_owned(),
             HasIntervalColoring => "Has some interval coloring".to_owned(),
             IsPlanar => "Is planar".to_owned(),
+            IsHamiltonian => "Is Hamiltonian".to_owned(),
+            IsTraceable => "Has a Hamiltonian path".to_owned(),
+            IsHamiltonConnected => "Is Hamilton-connected".to_owned(),
             IsRegular => "Is regular".to_owned(),
             BunkbedDiffsAllUnimodal => "Bunkbed diff polys are all unimodal".to_owned(),
             HasRegularLosslessEdgeDominator => {
@@ -456,6 +493,11 @@ impl fmt::Display for BoolOperation {
                 "Can be embedded as d-distance in hyperbolic plane".to_owned()
             }
             HasFewEdgesHyperbolic => "Has at most 2n-3 edges hereditarily".to_owned(),
+            ContainsSubgraph(h) => format!("Contains {} as a subgraph", h),
+            ContainsInduced(h) => format!("Contains {} as an induced subgraph", h),
+            IsUnimodal(op) => format!("{} has unimodal coefficients", op),
+            IsLogConcave(op) => format!("{} has log-concave coefficients", op),
+            IsRealRooted(op) => format!("{} is real-rooted", op),
             Debug => "Returns true if some debugging tests trip".to_owned(),
         };
         write!(f, "{}", name)
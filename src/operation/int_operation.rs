@@ -2,7 +2,9 @@ use std::fmt;
 
 use utilities::parse_function_like;
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug, PartialOrd, Ord)]
+use crate::operation::subgraph_pattern::*;
+
+#[derive(Eq, Hash, PartialEq, Clone, Debug, PartialOrd, Ord)]
 pub enum IntOperation {
     Order,
     Size,
@@ -47,8 +49,19 @@ pub enum IntOperation {
     MaxMatching,
     MinNorineDistance(usize),
     MinKernelSize,
+    MinOutDegree,
     MaxCodegree,
     OneFactorSubsets,
+    NumSubgraphs(SubgraphPattern),
+    NumInducedSubgraphs(SubgraphPattern),
+    NumSpanningTrees,
+    NumSpanningForests,
+    NumAcyclicOrientations,
+    NumDistinctEigenvalues,
+    Circumference,
+    LongestPath,
+    Treewidth,
+    Pathwidth,
     Number(u32),
 }
 
@@ -105,9 +118,24 @@ impl IntOperation {
             "num_extensions" => Some(NumLinearExtensions),
             "max_matching" => Some(MaxMatching),
             "min_kernel" => Some(MinKernelSize),
+            "min_out_degree" | "min_out_deg" => Some(MinOutDegree),
             "norine" => Some(MinNorineDistance(args[0].parse().unwrap_or(0))),
             "max_codeg" => Some(MaxCodegree),
             "one_factor" => Some(OneFactorSubsets),
+            "num_subgraphs" | "num_copies" => {
+                Some(NumSubgraphs(SubgraphPattern::of_string(args.first()?)))
+            }
+            "num_induced" | "num_induced_copies" => Some(NumInducedSubgraphs(
+                SubgraphPattern::of_string(args.first()?),
+            )),
+            "spanning_trees" | "num_trees" | "tau" => Some(NumSpanningTrees),
+            "spanning_forests" | "num_forests" => Some(NumSpanningForests),
+            "acyclic_orientations" | "num_acyclic" => Some(NumAcyclicOrientations),
+            "distinct_eigenvalues" | "num_eigenvalues" => Some(NumDistinctEigenvalues),
+            "circumference" | "longest_cycle" => Some(Circumference),
+            "longest_path" => Some(LongestPath),
+            "treewidth" | "tw" => Some(Treewidth),
+            "pathwidth" | "pw" => Some(Pathwidth),
             str => str.parse().ok().map(Number),
         }
     }
@@ -165,8 +193,25 @@ impl fmt::Display for IntOperation {
                 "Min Norine distance between antipodes in random colouring of Q_n"
             }
             MinKernelSize => "Min size of a 2-kernel in digraph",
+            MinOutDegree => "Min out-degree",
             MaxCodegree => "Max codegree",
             OneFactorSubsets => "One-factorisations connectivity subsets",
+            NumSubgraphs(h) => {
+                sta = format!("Number of copies of {}", h);
+                sta.as_str()
+            }
+            NumInducedSubgraphs(h) => {
+                sta = format!("Number of induced copies of {}", h);
+                sta.as_str()
+            }
+            NumSpanningTrees => "Number of spanning trees",
+            NumSpanningForests => "Number of spanning forests",
+            NumAcyclicOrientations => "Number of acyclic orientations",
+            NumDistinctEigenvalues => "Number of distinct adjacency eigenvalues",
+            Circumference => "Circumference",
+            LongestPath => "Length of longest path",
+            Treewidth => "Treewidth",
+            Pathwidth => "Pathwidth",
             Number(n) => {
                 sta = n.to_string();
                 sta.as_str()
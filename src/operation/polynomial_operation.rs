@@ -0,0 +1,44 @@
+use std::fmt;
+
+use utilities::parse_function_like;
+
+#[derive(Eq, Hash, PartialEq, Clone, Copy, Debug, PartialOrd, Ord)]
+pub enum PolynomialOperation {
+    Chromatic,
+    Independence,
+    Matching,
+    Clique,
+    Domination,
+    Reliability,
+}
+
+impl PolynomialOperation {
+    pub fn of_string_result(text: &str) -> Option<Self> {
+        use PolynomialOperation::*;
+        let (func, _args) = parse_function_like(text);
+        match func.trim().to_lowercase().as_str() {
+            "chromatic_polynomial" | "chi_poly" => Some(Chromatic),
+            "independence_polynomial" | "indep_poly" => Some(Independence),
+            "matching_polynomial" | "match_poly" => Some(Matching),
+            "clique_polynomial" | "clique_poly" => Some(Clique),
+            "domination_polynomial" | "dom_poly" => Some(Domination),
+            "reliability_polynomial" | "reliability" | "rel_poly" => Some(Reliability),
+            &_ => None,
+        }
+    }
+}
+
+impl fmt::Display for PolynomialOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PolynomialOperation::*;
+        let name = match self {
+            Chromatic => "Chromatic polynomial",
+            Independence => "Independence polynomial",
+            Matching => "Matching polynomial",
+            Clique => "Clique polynomial",
+            Domination => "Domination polynomial",
+            Reliability => "All-terminal reliability polynomial",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -25,6 +25,11 @@ pub enum RationalOperation {
     PosetCapBalance,
     PosetBalanceWithMinimal,
     NorineAverageDistance(usize),
+    LargestEigenvalue,
+    AlgebraicConnectivity,
+    SpectralGap,
+    Energy,
+    LaplacianEigenvalue(usize),
 }
 
 impl RationalOperation {
@@ -59,6 +64,13 @@ impl RationalOperation {
                     "poset_cap_balance" => Some(PosetCapBalance),
                     "balance_with_min" => Some(PosetBalanceWithMinimal),
                     "avg_norine" => Some(NorineAverageDistance(args[0].parse().unwrap_or(0))),
+                    "largest_eigenvalue" | "spectral_radius" | "lambda" => Some(LargestEigenvalue),
+                    "algebraic_connectivity" | "fiedler" => Some(AlgebraicConnectivity),
+                    "spectral_gap" => Some(SpectralGap),
+                    "energy" => Some(Energy),
+                    "laplacian_eigenvalue" | "mu" => {
+                        Some(LaplacianEigenvalue(args[0].parse().unwrap()))
+                    }
                     &_ => IntOperation::of_string_result(text).map(OfInt),
                 }
             }
@@ -102,6 +114,11 @@ impl fmt::Display for RationalOperation {
             PosetCapBalance => "Balance constant as the cap of a ladder".to_owned(),
             PosetBalanceWithMinimal => "Balance among pairs including a min".to_owned(),
             NorineAverageDistance(_) => "Average Norine distance between antipodes".to_owned(),
+            LargestEigenvalue => "Largest adjacency eigenvalue".to_owned(),
+            AlgebraicConnectivity => "Algebraic connectivity".to_owned(),
+            SpectralGap => "Spectral gap".to_owned(),
+            Energy => "Graph energy".to_owned(),
+            LaplacianEigenvalue(k) => format!("Laplacian eigenvalue {} (from 0)", k),
         };
         write!(f, "{}", name)
     }
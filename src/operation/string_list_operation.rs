@@ -6,6 +6,9 @@ use utilities::parse_function_like;
 pub enum StringListOperation {
     BunkbedSiteSignatures,
     Serialise,
+    Graph6,
+    Sparse6,
+    Digraph6,
 }
 
 impl StringListOperation {
@@ -15,6 +18,9 @@ impl StringListOperation {
         match func.trim().to_lowercase().as_str() {
             "bbss" => Some(BunkbedSiteSignatures),
             "serialise" | "code" => Some(Serialise),
+            "graph6" | "g6" => Some(Graph6),
+            "sparse6" | "s6" => Some(Sparse6),
+            "digraph6" | "d6" => Some(Digraph6),
             &_ => None,
         }
     }
@@ -26,6 +32,9 @@ impl fmt::Display for StringListOperation {
         let name = match self {
             BunkbedSiteSignatures => "Bunkbed site reachability signatures",
             Serialise => "Serialise graph to a string",
+            Graph6 => "graph6 encoding",
+            Sparse6 => "sparse6 encoding",
+            Digraph6 => "digraph6 encoding",
         };
         write!(f, "{}", name)
     }
@@ -0,0 +1,69 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::constructor::Constructor;
+use crate::entity::graph::Graph;
+
+// The graph H to look for in subgraph operations. It is built once, when
+// the operation is parsed, so that a random constructor gives the same H
+// for every graph the operation is applied to. Two patterns are the same
+// operation exactly when their graphs are isomorphic.
+
+#[derive(Clone)]
+pub struct SubgraphPattern {
+    pub graph: Box<Graph>,
+    text: String,
+    canonical_form: String,
+}
+
+impl SubgraphPattern {
+    pub fn of_string(text: &str) -> Self {
+        let text = text.trim();
+        let graph = Box::new(Constructor::of_string(text).new_entity().as_owned_graph());
+        let canonical_form = graph.canonical_form();
+        SubgraphPattern {
+            graph,
+            text: text.to_owned(),
+            canonical_form,
+        }
+    }
+}
+
+impl PartialEq for SubgraphPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_form == other.canonical_form
+    }
+}
+
+impl Eq for SubgraphPattern {}
+
+impl Hash for SubgraphPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_form.hash(state);
+    }
+}
+
+impl PartialOrd for SubgraphPattern {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SubgraphPattern {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_form.cmp(&other.canonical_form)
+    }
+}
+
+impl fmt::Debug for SubgraphPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SubgraphPattern({})", self.text)
+    }
+}
+
+impl fmt::Display for SubgraphPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
@@ -43,6 +43,11 @@ pub enum UnitOperation {
     PrettyPrint,
     PrettyPrintCyclic,
     Signature,
+    PrintTutte,
+    PrintSpectra,
+    PrintHamiltonianCycle,
+    PrintTreeDecomposition,
+    PrintPathDecomposition,
     Unit,
 }
 
@@ -116,6 +121,11 @@ impl UnitOperation {
             "pskn" => Some(PrintSiteKozmaNitzan),
             "pretty" => Some(PrettyPrint),
             "pretty_cyclic" => Some(PrettyPrintCyclic),
+            "tutte" | "tutte_polynomial" => Some(PrintTutte),
+            "spectrum" | "spectra" => Some(PrintSpectra),
+            "print_hamiltonian" | "hamiltonian_cycle" => Some(PrintHamiltonianCycle),
+            "tree_decomposition" | "print_td" => Some(PrintTreeDecomposition),
+            "path_decomposition" | "print_pd" => Some(PrintPathDecomposition),
             "()" | "(" => Some(Unit),
             &_ => None,
         }
@@ -195,6 +205,11 @@ impl fmt::Display for UnitOperation {
             PrettyPrintCyclic => "Produce cyclic image of the graph",
             PrintKozmaNitzan => "Print the Kozma-Nitzan probabilities",
             PrintSiteKozmaNitzan => "Print the site Kozma-Nitzan probabilities",
+            PrintTutte => "Print the Tutte polynomial",
+            PrintSpectra => "Print the adjacency and Laplacian spectra",
+            PrintHamiltonianCycle => "Print a Hamiltonian cycle (if exists)",
+            PrintTreeDecomposition => "Print a tree decomposition of minimum width",
+            PrintPathDecomposition => "Print a path decomposition of minimum width",
             Unit => "Do nothing",
         };
         write!(f, "{}", name)
@@ -10,11 +10,13 @@ use rand::prelude::SliceRandom;
 use rand::thread_rng;
 
 mod bowties;
+mod leaves;
 mod triple_pentagons;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum VertexPattern {
     Bowties(Degree),
+    MergedLeaves(String, Degree),
 }
 
 pub struct GraphWithVertices {
@@ -24,9 +26,10 @@ pub struct GraphWithVertices {
     num_verts: usize,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum EdgePattern {
     TriplePentagons,
+    PendantEdges(String),
 }
 
 pub struct GraphWithEdges {
@@ -40,7 +43,7 @@ impl VertexPattern {
     fn num_around_vertex(&self) -> usize {
         use VertexPattern::*;
         match self {
-            Bowties(d) => d.to_usize(),
+            Bowties(d) | MergedLeaves(_, d) => d.to_usize(),
         }
     }
 
@@ -50,16 +53,30 @@ impl VertexPattern {
 
         match pattern.to_lowercase().as_str() {
             "bowties" => Some(Bowties(Degree::of_string(args[0]))),
+            "merge" | "merge_leaves" => {
+                let filename = args[0].trim().to_owned();
+                let degree = match args.get(1) {
+                    Some(d) => Degree::of_string(d),
+                    None => leaves::common_degree(&filename),
+                };
+                Some(MergedLeaves(filename, degree))
+            }
             _ => None,
         }
     }
 
     pub fn new_graph(&self, num: usize) -> Graph {
-        let gwv = GraphWithVertices::new(self);
+        self.new_graph_of(GraphWithVertices::new(self), num)
+    }
+
+    fn new_graph_of(&self, gwv: GraphWithVertices, num: usize) -> Graph {
         let nav = self.num_around_vertex();
         if (gwv.num_verts * num) % nav != 0 {
             panic!("Parity does not work!");
         }
+        if num < nav {
+            panic!("Need at least {} copies to glue {} at a time!", nav, nav);
+        }
         let h_n = gwv.h.n.to_usize();
         let num_internal_per_h = h_n - gwv.num_verts;
         let num_extra_verts = (gwv.num_verts * num) / nav;
@@ -120,7 +137,7 @@ impl VertexPattern {
         use crate::constructor::*;
         Graph::of_adj_list(
             adj_list,
-            Constructor::Random(RandomConstructor::VertexStructured(*self, num)),
+            Constructor::Random(RandomConstructor::VertexStructured(self.to_owned(), num)),
         )
     }
 }
@@ -130,6 +147,7 @@ impl GraphWithVertices {
         use VertexPattern::*;
         match pattern {
             Bowties(d) => bowties::new(d),
+            MergedLeaves(filename, _) => leaves::new_merged(filename),
         }
     }
 }
@@ -138,21 +156,25 @@ impl EdgePattern {
     fn num_around_edge(&self) -> usize {
         use EdgePattern::*;
         match self {
-            TriplePentagons => 2,
+            TriplePentagons | PendantEdges(_) => 2,
         }
     }
 
     pub fn of_string(text: &str) -> Option<Self> {
-        let (pattern, _args) = parse_function_like(text);
+        let (pattern, args) = parse_function_like(text);
         use EdgePattern::*;
 
         match pattern.to_lowercase().as_str() {
             "triple_pentagons" | "pentagons" | "pents" => Some(TriplePentagons),
+            "glue" | "top_to_tail" => Some(PendantEdges(args[0].trim().to_owned())),
             _ => None,
         }
     }
 
     pub fn new_graph(&self, num: usize) -> Graph {
+        if let EdgePattern::PendantEdges(filename) = self {
+            return leaves::new_top_to_tail(self, filename, num);
+        }
         let gwe = GraphWithEdges::new(self);
         let nae = self.num_around_edge();
         if (gwe.num_edges * num) % nae != 0 {
@@ -232,7 +254,7 @@ impl EdgePattern {
         use crate::constructor::*;
         Graph::of_adj_list(
             adj_list,
-            Constructor::Random(RandomConstructor::EdgeStructured(*self, num)),
+            Constructor::Random(RandomConstructor::EdgeStructured(self.to_owned(), num)),
         )
     }
 }
@@ -242,6 +264,7 @@ impl GraphWithEdges {
         use EdgePattern::*;
         match pattern {
             TriplePentagons => triple_pentagons::new(),
+            PendantEdges(_) => panic!("Pendant edges are glued top-to-tail, not along edges!"),
         }
     }
 }
@@ -251,6 +274,7 @@ impl fmt::Display for VertexPattern {
         use VertexPattern::*;
         match self {
             Bowties(d) => write!(f, "Bowties of degree {}", d),
+            MergedLeaves(filename, d) => write!(f, "{} (leaves merged {} at a time)", filename, d),
         }
     }
 }
@@ -260,6 +284,7 @@ impl fmt::Display for EdgePattern {
         use EdgePattern::*;
         match self {
             TriplePentagons => write!(f, "Triple pentagons"),
+            PendantEdges(filename) => write!(f, "{} (top-to-tail)", filename),
         }
     }
 }
@@ -0,0 +1,204 @@
+use crate::constructor::from_file;
+use crate::pattern::*;
+
+// Patterns read from manual/{filename}.pat, with some highlighted leaves.
+// Copies are glued either by merging leaves from different copies into
+// single vertices, or by joining the pendant edges top-to-tail, so that the
+// neighbour of each leaf is joined to the neighbour of a leaf in another
+// copy.
+
+const MAX_SHUFFLES: usize = 100_000;
+
+fn split_pattern(h: &Graph, leaves: &[Vertex]) -> VertexVec<Vertex> {
+    if let Some(v) = leaves.iter().find(|v| h.deg[**v] != Degree::of_usize(1)) {
+        panic!("Highlighted vertex {} is not a leaf!", v);
+    }
+    h.iter_verts().filter(|v| !leaves.contains(v)).collect()
+}
+
+/**
+ * The degree that the merged leaves need for the glued graph to be
+ * regular, i.e. the common degree of the other vertices of the pattern.
+ */
+pub fn common_degree(filename: &str) -> Degree {
+    let (h, leaves) = from_file::read_pattern(filename);
+    let others = split_pattern(&h, &leaves);
+    let degree = h.deg[others[Vertex::ZERO]];
+    if others.iter().any(|v| h.deg[*v] != degree) {
+        panic!(
+            "The unhighlighted vertices of {} have different degrees!",
+            filename
+        );
+    }
+    degree
+}
+
+fn merged_of_pattern(h: Graph, leaves: Vec<Vertex>) -> GraphWithVertices {
+    let other_verts = split_pattern(&h, &leaves);
+    GraphWithVertices {
+        h,
+        num_verts: leaves.len(),
+        verts: leaves,
+        other_verts,
+    }
+}
+
+pub fn new_merged(filename: &str) -> GraphWithVertices {
+    let (h, leaves) = from_file::read_pattern(filename);
+    merged_of_pattern(h, leaves)
+}
+
+fn top_to_tail_of_pattern(h: &Graph, leaves: &[Vertex], num: usize) -> VertexVec<Vec<Vertex>> {
+    let other_verts = split_pattern(h, leaves);
+    let num_internal = other_verts.len().to_usize();
+    let mut other_verts_inv = VertexVec::new(h.n, &Vertex::ZERO);
+    for (i, x) in other_verts.iter_enum() {
+        other_verts_inv[*x] = i;
+    }
+    if !(leaves.len() * num).is_multiple_of(2) {
+        panic!("Parity does not work!");
+    }
+    if num < 2 {
+        panic!("Need at least 2 copies to join leaves top to tail!");
+    }
+
+    let n = Order::of_usize(num_internal * num);
+    let mut adj_list: VertexVec<Vec<Vertex>> = VertexVec::new(n, &vec![]);
+    for h_index in 0..num {
+        let offset = h_index * num_internal;
+        for v in other_verts.iter() {
+            for w in h.adj_list[*v].iter() {
+                if !leaves.contains(w) {
+                    adj_list[other_verts_inv[*v].incr_by(offset)]
+                        .push(other_verts_inv[*w].incr_by(offset));
+                }
+            }
+        }
+    }
+
+    // Each stub is the neighbour of a leaf in some copy.
+    let mut stubs: Vec<(usize, Vertex)> = vec![];
+    for h_index in 0..num {
+        for leaf in leaves.iter() {
+            let x = other_verts_inv[h.adj_list[*leaf][0]];
+            stubs.push((h_index, x.incr_by(h_index * num_internal)));
+        }
+    }
+    // The stubs at a vertex must go to different vertices in other copies,
+    // or some edge is added twice.
+    let mut num_stubs_at = VertexVec::new(h.n, &0);
+    for leaf in leaves.iter() {
+        num_stubs_at[h.adj_list[*leaf][0]] += 1;
+    }
+    let num_stub_verts = num_stubs_at.iter().filter(|k| **k > 0).count();
+    if num_stubs_at.iter().any(|k| *k > (num - 1) * num_stub_verts) {
+        panic!("Every way of joining the leaves top to tail repeats an edge!");
+    }
+    let half = stubs.len() / 2;
+    let mut rng = thread_rng();
+
+    'find_good_shuffle: for _ in 0..MAX_SHUFFLES {
+        stubs.shuffle(&mut rng);
+        let mut new_edges: Vec<(Vertex, Vertex)> = vec![];
+        for i in 0..half {
+            let ((copy_u, u), (copy_v, v)) = (stubs[i], stubs[i + half]);
+            if copy_u == copy_v || new_edges.contains(&(u, v)) || new_edges.contains(&(v, u)) {
+                continue 'find_good_shuffle;
+            }
+            new_edges.push((u, v));
+        }
+        for (u, v) in new_edges {
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+        return adj_list;
+    }
+    panic!(
+        "No way of joining the leaves top to tail found after {} attempts!",
+        MAX_SHUFFLES
+    );
+}
+
+pub fn new_top_to_tail(pattern: &EdgePattern, filename: &str, num: usize) -> Graph {
+    let (h, leaves) = from_file::read_pattern(filename);
+    use crate::constructor::*;
+    Graph::of_adj_list(
+        top_to_tail_of_pattern(&h, &leaves, num),
+        Constructor::Random(RandomConstructor::EdgeStructured(pattern.to_owned(), num)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * K_4 minus an edge, with a leaf hanging off each of the two vertices of
+     * degree 2.
+     */
+    fn diamond() -> (Graph, Vec<Vertex>) {
+        let (h, leaves) = from_file::read_pattern("diamond");
+        assert_eq!(h.n.to_usize(), 6);
+        assert_eq!(h.size(), 7);
+        assert_eq!(leaves, vec![Vertex::of_usize(4), Vertex::of_usize(5)]);
+        assert!(leaves.iter().all(|v| h.deg[*v] == Degree::of_usize(1)));
+        (h, leaves)
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 2 copies")]
+    fn test_top_to_tail_needs_two_copies() {
+        let (h, leaves) = diamond();
+        top_to_tail_of_pattern(&h, &leaves, 1);
+    }
+
+    #[test]
+    fn test_top_to_tail() {
+        let (h, leaves) = diamond();
+        for num in [2, 5, 12] {
+            let adj_list = top_to_tail_of_pattern(&h, &leaves, num);
+            assert_eq!(adj_list.len().to_usize(), 4 * num);
+            assert!(adj_list.iter().all(|nbrs| nbrs.len() == 3));
+        }
+    }
+
+    /**
+     * A vertex with two leaves and one other neighbour.
+     */
+    fn cherry() -> (Graph, Vec<Vertex>) {
+        let adj_list = vec![vec![1, 2, 3], vec![0], vec![0], vec![0]];
+        let h = Graph::of_adj_list(
+            crate::entity::graph::adj_list_of_manual(adj_list),
+            crate::constructor::Constructor::Special,
+        );
+        (h, vec![Vertex::of_usize(1), Vertex::of_usize(2)])
+    }
+
+    #[test]
+    #[should_panic(expected = "repeats an edge")]
+    fn test_top_to_tail_repeated_stubs() {
+        // Both leaves of one copy would have to go to the same vertex.
+        let (h, leaves) = cherry();
+        top_to_tail_of_pattern(&h, &leaves, 2);
+    }
+
+    #[test]
+    fn test_top_to_tail_cherries() {
+        // With three copies the centres must form a triangle.
+        let (h, leaves) = cherry();
+        let adj_list = top_to_tail_of_pattern(&h, &leaves, 3);
+        assert_eq!(adj_list.len().to_usize(), 6);
+        assert_eq!(adj_list.iter().filter(|nbrs| nbrs.len() == 3).count(), 3);
+    }
+
+    #[test]
+    fn test_merged_leaves() {
+        let (h, leaves) = diamond();
+        let pattern = VertexPattern::MergedLeaves("diamond".to_owned(), Degree::of_usize(3));
+        let gwv = merged_of_pattern(h, leaves);
+        assert_eq!(gwv.other_verts.len().to_usize(), 4);
+        let g = pattern.new_graph_of(gwv, 6);
+        assert_eq!(g.n.to_usize(), 6 * 4 + 4);
+        assert!(g.deg.iter().all(|d| *d == Degree::of_usize(3)));
+    }
+}
@@ -0,0 +1,73 @@
+// Dense linear algebra for the small matrices that come out of graphs.
+// Eigenvalues of real symmetric matrices are found with the cyclic Jacobi
+// method, which is slow for big matrices but very accurate, and needs no
+// more than rotations.
+
+const MAX_SWEEPS: usize = 100;
+const TOLERANCE: f64 = 1e-12;
+
+fn off_diagonal_norm(a: &[Vec<f64>]) -> f64 {
+    let mut sum = 0.0;
+    for (i, row) in a.iter().enumerate() {
+        for (j, x) in row.iter().enumerate() {
+            if i != j {
+                sum += x * x;
+            }
+        }
+    }
+    sum.sqrt()
+}
+
+/**
+ * Conjugates a by the rotation in the (p, q)-plane which kills a[p][q].
+ */
+fn rotate(a: &mut [Vec<f64>], p: usize, q: usize) {
+    let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+    let c = 1.0 / (t * t + 1.0).sqrt();
+    let s = t * c;
+    for row in a.iter_mut() {
+        let (akp, akq) = (row[p], row[q]);
+        row[p] = c * akp - s * akq;
+        row[q] = s * akp + c * akq;
+    }
+    let (before_q, from_q) = a.split_at_mut(q);
+    for (apk, aqk) in before_q[p].iter_mut().zip(from_q[0].iter_mut()) {
+        (*apk, *aqk) = (c * *apk - s * *aqk, s * *apk + c * *aqk);
+    }
+}
+
+/**
+ * The eigenvalues of a real symmetric matrix, in increasing order and
+ * with multiplicity.
+ */
+pub fn symmetric_eigenvalues(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let n = matrix.len();
+    for (i, row) in matrix.iter().enumerate() {
+        if row.len() != n {
+            panic!("Matrix is not square!");
+        }
+        for (j, x) in row.iter().enumerate() {
+            if *x != matrix[j][i] {
+                panic!("Matrix is not symmetric!");
+            }
+        }
+    }
+    let mut a = matrix.to_owned();
+    let scale = matrix.iter().flatten().map(|x| x * x).sum::<f64>().sqrt();
+    for _ in 0..MAX_SWEEPS {
+        if off_diagonal_norm(&a) <= TOLERANCE * scale {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q] != 0.0 {
+                    rotate(&mut a, p, q);
+                }
+            }
+        }
+    }
+    let mut eigenvalues: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+    eigenvalues.sort_by(|x, y| x.total_cmp(y));
+    eigenvalues
+}
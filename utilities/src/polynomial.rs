@@ -1,5 +1,7 @@
 use std::fmt;
 
+pub mod bivariate;
+mod roots;
 mod unimode;
 
 #[derive(Clone, Debug)]
@@ -132,10 +134,62 @@ impl Polynomial {
         Self::of_vec(&coefs)
     }
 
+    pub fn coefs(&self) -> &[i64] {
+        &self.coefs
+    }
+
     pub fn is_zero(&self) -> bool {
         self.coefs.iter().all(|x| *x == 0)
     }
 
+    /**
+     * The coefficients from the lowest non-zero one to the highest, in
+     * absolute value.
+     */
+    fn abs_coef_support(&self) -> &[i64] {
+        match self.coefs.iter().position(|x| *x != 0) {
+            Some(start) => {
+                let end = self.coefs.iter().rposition(|x| *x != 0).unwrap();
+                &self.coefs[start..=end]
+            }
+            None => &[],
+        }
+    }
+
+    /**
+     * Whether the absolute values of the coefficients rise and then fall.
+     */
+    pub fn are_coefs_unimodal(&self) -> bool {
+        let coefs: Vec<u64> = self
+            .abs_coef_support()
+            .iter()
+            .map(|x| x.unsigned_abs())
+            .collect();
+        let peak = coefs.windows(2).take_while(|w| w[0] <= w[1]).count();
+        coefs[peak..].windows(2).all(|w| w[0] >= w[1])
+    }
+
+    /**
+     * Whether the absolute values of the coefficients have no internal
+     * zeros and satisfy a_k^2 >= a_(k - 1) a_(k + 1).
+     */
+    pub fn are_coefs_log_concave(&self) -> bool {
+        let coefs: Vec<u128> = self
+            .abs_coef_support()
+            .iter()
+            .map(|x| x.unsigned_abs() as u128)
+            .collect();
+        coefs.iter().all(|x| *x != 0) && coefs.windows(3).all(|w| w[1] * w[1] >= w[0] * w[2])
+    }
+
+    /**
+     * Whether every root is real, checked exactly with a Sturm sequence.
+     */
+    pub fn is_real_rooted(&self) -> bool {
+        let (num_real, num_distinct) = roots::count_distinct_roots(self);
+        num_real == num_distinct
+    }
+
     pub fn find_prob_unimode(&self) -> Modality {
         unimode::find_unimode(&self, 0.0, 1.0)
     }
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::polynomial::*;
+
+// Polynomials in two variables x and y with integer coefficients, stored as
+// coefs[i][j] for the coefficient of x^i y^j. The rows may be padded with
+// zeros, so equality compares the coefficients rather than the layout.
+// Arithmetic panics on overflow rather than wrapping.
+
+#[derive(Clone, Debug, Default)]
+pub struct BivariatePolynomial {
+    coefs: Vec<Vec<i64>>,
+}
+
+impl BivariatePolynomial {
+    pub fn new() -> Self {
+        Self { coefs: vec![] }
+    }
+
+    pub fn monomial(coef: i64, x_power: usize, y_power: usize) -> Self {
+        let mut coefs = vec![vec![]; x_power + 1];
+        coefs[x_power] = vec![0; y_power + 1];
+        coefs[x_power][y_power] = coef;
+        Self { coefs }
+    }
+
+    pub fn coef(&self, x_power: usize, y_power: usize) -> i64 {
+        self.coefs
+            .get(x_power)
+            .and_then(|row| row.get(y_power))
+            .map_or(0, |c| *c)
+    }
+
+    pub fn add_inplace(&mut self, rhs: &Self) {
+        if self.coefs.len() < rhs.coefs.len() {
+            self.coefs.resize(rhs.coefs.len(), vec![]);
+        }
+        for (row, rhs_row) in self.coefs.iter_mut().zip(rhs.coefs.iter()) {
+            if row.len() < rhs_row.len() {
+                row.resize(rhs_row.len(), 0);
+            }
+            for (c, d) in row.iter_mut().zip(rhs_row.iter()) {
+                *c = c
+                    .checked_add(*d)
+                    .expect("Overflow in bivariate polynomial!");
+            }
+        }
+    }
+
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut sum = self.to_owned();
+        sum.add_inplace(rhs);
+        sum
+    }
+
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let mut product = Self::new();
+        for (i, row) in self.coefs.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                if *c == 0 {
+                    continue;
+                }
+                for (k, rhs_row) in rhs.coefs.iter().enumerate() {
+                    for (l, d) in rhs_row.iter().enumerate() {
+                        if *d != 0 {
+                            let coef = c
+                                .checked_mul(*d)
+                                .expect("Overflow in bivariate polynomial!");
+                            product.add_inplace(&Self::monomial(coef, i + k, j + l));
+                        }
+                    }
+                }
+            }
+        }
+        product
+    }
+
+    pub fn evaluate(&self, x: i64, y: i64) -> i64 {
+        let mut total: i64 = 0;
+        for (i, row) in self.coefs.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                total = x
+                    .checked_pow(i as u32)
+                    .zip(y.checked_pow(j as u32))
+                    .and_then(|(x_i, y_j)| c.checked_mul(x_i)?.checked_mul(y_j))
+                    .and_then(|term| total.checked_add(term))
+                    .expect("Overflow evaluating bivariate polynomial!");
+            }
+        }
+        total
+    }
+
+    /**
+     * Sets x to the given value, leaving a polynomial in y.
+     */
+    pub fn specialise_x(&self, x: i64) -> Polynomial {
+        let mut poly = Polynomial::new();
+        for (i, row) in self.coefs.iter().enumerate() {
+            poly.add_inplace(
+                &Polynomial::of_vec(row).mul(&Polynomial::of_vec(&vec![x.pow(i as u32)])),
+            );
+        }
+        poly.with_var_name("y")
+    }
+}
+
+impl PartialEq for BivariatePolynomial {
+    fn eq(&self, other: &Self) -> bool {
+        let x_len = self.coefs.len().max(other.coefs.len());
+        (0..x_len).all(|i| {
+            let y_len = [self, other]
+                .iter()
+                .map(|p| p.coefs.get(i).map_or(0, |row| row.len()))
+                .max()
+                .unwrap();
+            (0..y_len).all(|j| self.coef(i, j) == other.coef(i, j))
+        })
+    }
+}
+
+impl Eq for BivariatePolynomial {}
+
+impl fmt::Display for BivariatePolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut pars: Vec<String> = vec![];
+        for (i, row) in self.coefs.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                if *c != 0 {
+                    pars.push(format!("{}x^{}y^{}", c, i, j));
+                }
+            }
+        }
+        if pars.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", pars.join(" + "))
+        }
+    }
+}
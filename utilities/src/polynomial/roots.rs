@@ -0,0 +1,129 @@
+use num_bigint::{BigInt, Sign};
+
+use crate::polynomial::*;
+
+// Exact root counting with Sturm sequences. The remainders are computed as
+// pseudo-remainders over arbitrary precision integers, with their content
+// divided out so that the coefficients grow as little as possible.
+
+type Coefs = Vec<BigInt>;
+
+fn trim(coefs: &mut Coefs) {
+    while coefs.last().is_some_and(|x| x.sign() == Sign::NoSign) {
+        coefs.pop();
+    }
+}
+
+fn abs(x: &BigInt) -> BigInt {
+    if x.sign() == Sign::Minus {
+        -x
+    } else {
+        x.to_owned()
+    }
+}
+
+fn gcd(mut a: BigInt, mut b: BigInt) -> BigInt {
+    while b.sign() != Sign::NoSign {
+        let rem = &a % &b;
+        a = b;
+        b = rem;
+    }
+    abs(&a)
+}
+
+/**
+ * Divides through by the gcd of the coefficients, keeping the signs.
+ */
+fn primitive_part(coefs: &mut Coefs) {
+    let content = coefs
+        .iter()
+        .fold(BigInt::from(0), |g, x| gcd(g, x.to_owned()));
+    if content > BigInt::from(1) {
+        for x in coefs.iter_mut() {
+            *x /= &content;
+        }
+    }
+}
+
+/**
+ * Some positive multiple of the remainder of a on division by b.
+ */
+fn positive_remainder(a: &Coefs, b: &Coefs) -> Coefs {
+    let mut rem = a.to_owned();
+    let db = b.len() - 1;
+    let scale = abs(&b[db]);
+    while rem.len() > db {
+        let dr = rem.len() - 1;
+        let top = if b[db].sign() == Sign::Minus {
+            -&rem[dr]
+        } else {
+            rem[dr].to_owned()
+        };
+        // rem := |lead| rem - sign(lead) top x^(dr - db) b, which kills the
+        // top coefficient and only scales rem by a positive number.
+        for x in rem.iter_mut() {
+            *x *= &scale;
+        }
+        for (i, y) in b.iter().enumerate() {
+            rem[dr - db + i] -= &top * y;
+        }
+        trim(&mut rem);
+        primitive_part(&mut rem);
+    }
+    rem
+}
+
+fn sign_changes(signs: &[Sign]) -> usize {
+    let nonzero: Vec<&Sign> = signs.iter().filter(|x| **x != Sign::NoSign).collect();
+    nonzero.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+/**
+ * Returns the number of distinct real roots of f, and the number of
+ * distinct complex roots.
+ */
+pub fn count_distinct_roots(f: &Polynomial) -> (usize, usize) {
+    let mut p: Coefs = f.coefs.iter().map(|x| BigInt::from(*x)).collect();
+    trim(&mut p);
+    if p.len() <= 1 {
+        return (0, 0);
+    }
+    let mut dp: Coefs = p
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, x)| x * BigInt::from(i))
+        .collect();
+    primitive_part(&mut p);
+    primitive_part(&mut dp);
+    let mut sequence = vec![p, dp];
+    loop {
+        let len = sequence.len();
+        let mut rem = positive_remainder(&sequence[len - 2], &sequence[len - 1]);
+        if rem.is_empty() {
+            break;
+        }
+        for x in rem.iter_mut() {
+            *x = -&*x;
+        }
+        sequence.push(rem);
+    }
+    // The signs at +infinity and -infinity only depend on the leading terms.
+    let at_plus: Vec<Sign> = sequence.iter().map(|q| q[q.len() - 1].sign()).collect();
+    let at_minus: Vec<Sign> = sequence
+        .iter()
+        .map(|q| {
+            if q.len() % 2 == 0 {
+                -q[q.len() - 1].sign()
+            } else {
+                q[q.len() - 1].sign()
+            }
+        })
+        .collect();
+    let num_real = sign_changes(&at_minus) - sign_changes(&at_plus);
+    // The last term is the gcd of f and f', whose degree is the number of
+    // repeated roots counted with multiplicity minus one each.
+    let degree = sequence[0].len() - 1;
+    let gcd_degree = sequence[sequence.len() - 1].len() - 1;
+    (num_real, degree - gcd_degree)
+}
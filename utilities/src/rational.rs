@@ -37,6 +37,29 @@ impl Rational {
         Rational { numerator: self.numerator / (gcd as i64), denominator: self.denominator / gcd }
     }
 
+    /**
+     * The last convergent of the continued fraction of x whose denominator
+     * is at most max_denominator, so integers and simple fractions come
+     * out exactly.
+     */
+    pub fn approximate(x: f64, max_denominator: u64) -> Rational {
+        let (mut h, mut h_prev) = (x.floor() as i64, 1);
+        let (mut k, mut k_prev) = (1, 0);
+        let mut remainder = x - x.floor();
+        while remainder > 1e-12 {
+            let y = 1.0 / remainder;
+            let a = y.floor();
+            let k_next = a as u64 * k + k_prev;
+            if k_next > max_denominator {
+                break;
+            }
+            (h, h_prev) = (a as i64 * h + h_prev, h);
+            (k, k_prev) = (k_next, k);
+            remainder = y - a;
+        }
+        Rational { numerator: h, denominator: k }
+    }
+
     pub fn to_f64(self) -> f64 {
         self.numerator as f64 / self.denominator as f64
     }